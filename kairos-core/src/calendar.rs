@@ -1,12 +1,27 @@
 // kairos-core/src/calendar.rs
+use core::fmt::{self, Write};
+
+use crate::civil::{CivilDate, CivilDateTime, CivilTime};
+use crate::time::pad;
+use crate::tz::TimeZone;
 use crate::VInstant;
 
 #[repr(transparent)]
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
-pub struct CivilDays(i64); // days since 1970-01-01 (proleptic Gregorian)
+pub struct CivilDays(pub(crate) i64); // days since 1970-01-01 (proleptic Gregorian)
 
-#[derive(Clone, Copy, Debug)]
-enum Anchor { Utc, Local }
+impl CivilDays {
+    /// Day number `days` (negative before 1970-01-01).
+    #[inline(always)]
+    pub const fn new(days: i64) -> Self { Self(days) }
+
+    /// Days since 1970-01-01.
+    #[inline(always)]
+    pub const fn get(self) -> i64 { self.0 }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Anchor { Utc, Local }
 
 #[cfg(feature = "std")]
 type SmallString = std::string::String;
@@ -16,12 +31,12 @@ type SmallString = heapless::String<32>;
 
 // Howard Hinnant's date algorithms (adapted) — allocation-free, no_std-friendly.
 #[inline(always)]
-fn days_from_civil(mut y: i32, m: u32, d: u32) -> i64 {
+pub(crate) fn days_from_civil(y: i32, m: u32, d: u32) -> i64 {
     // return: days since 1970-01-01
     let d = d as i32;
-    y -= (m <= 2) as i32;
-    let era = (y as i64).div_euclid(400);
-    let yoe = (y as i64 - era * 400) as i64;
+    let y = y as i64 - (m <= 2) as i64; // i64 so that January of i32::MIN fits
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
     let m = m as i64;
     let doy = (153 * ((m + 9) % 12) + 2) / 5 + d as i64 - 1;
     let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
@@ -29,37 +44,108 @@ fn days_from_civil(mut y: i32, m: u32, d: u32) -> i64 {
 }
 
 #[inline(always)]
-fn civil_from_days(z: i64) -> (i32, u32, u32) {
+pub(crate) fn civil_from_days(z: i64) -> (i32, u32, u32) {
     // input: days since 1970-01-01
     let z = z + 719_468;
-    let era = (if z >= 0 { z } else { z - 146_096 }).div_euclid(146_097);
+    let era = z.div_euclid(146_097);
     let doe = z - era * 146_097;                      // [0, 146096]
     let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365; // [0,399]
-    let mut y = (yoe + era * 400) as i32;
+    let y = yoe + era * 400;
     let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
     let mp = (5 * doy + 2) / 153;                     // [0,11]
     let d = (doy - (153 * mp + 2) / 5 + 1) as u32;    // [1,31]
     let m = (mp + if mp < 10 { 3 } else { -9 }) as u32; // [1,12]
-    if m <= 2 { y += 1; }
-    (y, m, d)
+    ((y + (m <= 2) as i64) as i32, m, d)
 }
 
-#[derive(Clone, Copy, Debug)]
-pub struct Calendar {
-    epoch_days: CivilDays,   // where VInstant(0) anchors in the calendar
-    tz_offset_secs: i32,     // e.g., -10800 for UTC-03:00
-    anchor: Anchor,
+pub(crate) fn is_leap(y: i64) -> bool {
+    (y % 4 == 0 && y % 100 != 0) || y % 400 == 0
+}
+
+pub(crate) fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        2 if is_leap(y) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Why `Calendar::from_civil` could not produce an instant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CivilError {
+    /// A field is out of range, e.g. month 13, February 30, hour 24 or 10^9 ns.
+    InvalidDateTime,
+    /// Before the calendar's epoch, or too late for a `VInstant`.
+    OutOfRange,
+    /// The wall-clock time happened twice because clocks were set back; these are the
+    /// two instants it names.
+    Ambiguous { earlier: VInstant, later: VInstant },
+    /// The wall-clock time was skipped because clocks were set forward. `earlier` and
+    /// `later` read it with the offset after and before the change; the transition lies
+    /// between them.
+    Nonexistent { earlier: VInstant, later: VInstant },
+}
+
+impl fmt::Display for CivilError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CivilError::InvalidDateTime => f.write_str("invalid date or time"),
+            CivilError::OutOfRange => f.write_str("date outside the calendar's range"),
+            CivilError::Ambiguous { .. } => f.write_str("ambiguous local time"),
+            CivilError::Nonexistent { .. } => f.write_str("nonexistent local time"),
+        }
+    }
 }
-impl Default for Calendar {
+
+#[cfg(feature = "std")]
+impl std::error::Error for CivilError {}
+
+/// How a zone's local time maps back to UTC: the offsets (seconds) that apply.
+enum LocalOffset {
+    Unique(i32),
+    /// Clocks were set back: the offsets before and after, i.e. the earlier and the
+    /// later reading.
+    Ambiguous(i32, i32),
+    /// Clocks were set forward: the offsets before and after the gap.
+    Gap(i32, i32),
+}
+
+/// Resolve `local` (seconds since 1970-01-01 on the zone's wall clock). Zone offsets
+/// change at most once in two days in practice, so the offsets a day either side are
+/// the only candidates.
+fn local_offset(tz: &TimeZone<'_>, local: i64) -> LocalOffset {
+    let before = tz.offset_at(local - 86_400).utc_offset;
+    let after = tz.offset_at(local + 86_400).utc_offset;
+    let fits = |o: i32| tz.offset_at(local - i64::from(o)).utc_offset == o;
+    match (fits(before), fits(after)) {
+        (true, true) if before != after => LocalOffset::Ambiguous(before, after),
+        (true, _) => LocalOffset::Unique(before),
+        (_, true) => LocalOffset::Unique(after),
+        _ => LocalOffset::Gap(before, after),
+    }
+}
+
+/// Maps `VInstant`s to civil dates and times. `'tz` is the lifetime of the zone data
+/// when a `TimeZone` is attached; calendars with a fixed offset are `Calendar<'static>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Calendar<'tz> {
+    pub(crate) epoch_days: CivilDays,   // where VInstant(0) anchors in the calendar
+    pub(crate) tz_offset_secs: i32,     // e.g., -10800 for UTC-03:00
+    pub(crate) anchor: Anchor,
+    pub(crate) tz: Option<TimeZone<'tz>>, // overrides tz_offset_secs when set
+}
+impl Default for Calendar<'_> {
     fn default() -> Self {
         Self {
             epoch_days: CivilDays(0), // 1970-01-01
             tz_offset_secs: 0,        // UTC
-            anchor: Anchor::Utc
+            anchor: Anchor::Utc,
+            tz: None,
         }
     }
 }
-impl Calendar {
+impl<'tz> Calendar<'tz> {
     pub fn new() -> Self { Self::default() }
 
     /// Set the civil date that corresponds to VInstant(0).
@@ -76,62 +162,174 @@ impl Calendar {
     }
 
     /// Set a fixed timezone offset (seconds relative to UTC), e.g., -3h = -10800.
+    /// Replaces any `TimeZone`.
     pub fn with_tz_offset_secs(mut self, offset: i32) -> Self {
         self.tz_offset_secs = offset;
+        self.tz = None;
         self
     }
 
-    /// Convert a VInstant (ns) into (YYYY,MM,DD, hh,mm,ss, millis) in the selected timezone.
-    pub fn to_civil(&self, t: VInstant) -> (i32, u32, u32, u32, u32, u32, u32) {
-        // ns → seconds and remainder ns
-        let total_ns = t.0 as i128;
-        let mut total_s = (total_ns / 1_000_000_000) as i64;
-        let sub_ns = (total_ns % 1_000_000_000) as i64;
+    /// Follow an IANA zone instead of a fixed offset: historical offsets and DST
+    /// transitions apply in `to_civil`, `format` and `display`.
+    pub fn with_time_zone<'a>(self, tz: TimeZone<'a>) -> Calendar<'a> {
+        Calendar { epoch_days: self.epoch_days, tz_offset_secs: self.tz_offset_secs, anchor: self.anchor, tz: Some(tz) }
+    }
 
-        // apply timezone offset (may be negative)
-        if let Anchor::Utc = self.anchor {
-            total_s += self.tz_offset_secs as i64;
+    pub fn time_zone(&self) -> Option<TimeZone<'tz>> {
+        self.tz
+    }
+
+    /// UTC offset (seconds) in effect at `t`: the fixed offset, or the zone's.
+    ///
+    /// With a local epoch `t` already counts wall-clock time; the offset is then the
+    /// zone's for that wall-clock time, taking the earlier one where clocks were set back.
+    pub fn utc_offset_at(&self, t: VInstant) -> i32 {
+        self.zone_at(t).0
+    }
+
+    /// The UTC offset at `t` and, with a `TimeZone`, the zone's abbreviation for it.
+    pub(crate) fn zone_at(&self, t: VInstant) -> (i32, Option<&'tz str>) {
+        let Some(tz) = &self.tz else {
+            return (self.tz_offset_secs, None);
+        };
+        let secs = self.epoch_days.0 * 86_400 + (t.0 / 1_000_000_000) as i64;
+        let utc = match self.anchor {
+            Anchor::Utc => secs,
+            Anchor::Local => match local_offset(tz, secs) {
+                LocalOffset::Unique(o) | LocalOffset::Ambiguous(o, _) => secs - i64::from(o),
+                // A skipped wall-clock time keeps the offset from before the change.
+                LocalOffset::Gap(..) => secs - 86_400,
+            },
+        };
+        let zone = tz.offset_at(utc);
+        (zone.utc_offset, Some(zone.abbreviation))
+    }
+
+    /// The instant a calendar date and time names: the inverse of `to_civil`, with
+    /// nanosecond precision. Shorthand for `from_civil_datetime` that checks the fields.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_civil(&self, y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32, ns: u32) -> Result<VInstant, CivilError> {
+        let time = CivilTime::new(h, mi, s, ns)?;
+        self.from_civil_datetime(CivilDate::new(y, mo, d)?.and_time(time))
+    }
+
+    /// The instant a civil date and time names: the inverse of `to_civil_datetime`.
+    ///
+    /// The date and time are read in the calendar's zone (or fixed offset). With a
+    /// `TimeZone`, a wall-clock time that happened twice or never is reported as
+    /// `CivilError::Ambiguous` or `CivilError::Nonexistent`, carrying the candidate
+    /// instants so the caller can pick. With a local epoch, instants count wall-clock
+    /// time and every valid time maps to exactly one instant.
+    pub fn from_civil_datetime(&self, dt: CivilDateTime) -> Result<VInstant, CivilError> {
+        let local = dt.local_secs();
+        let ns = dt.time().nanosecond();
+        let instant = |secs: i64| self.instant_at(secs, ns);
+        let tz = match (self.anchor, &self.tz) {
+            (Anchor::Local, _) => return instant(local),
+            (Anchor::Utc, None) => return instant(local - i64::from(self.tz_offset_secs)),
+            (Anchor::Utc, Some(tz)) => tz,
+        };
+        match local_offset(tz, local) {
+            LocalOffset::Unique(o) => instant(local - i64::from(o)),
+            LocalOffset::Ambiguous(before, after) => Err(CivilError::Ambiguous {
+                earlier: instant(local - i64::from(before))?,
+                later: instant(local - i64::from(after))?,
+            }),
+            LocalOffset::Gap(before, after) => Err(CivilError::Nonexistent {
+                earlier: instant(local - i64::from(after))?,
+                later: instant(local - i64::from(before))?,
+            }),
         }
+    }
 
-        // split into days and seconds-of-day
-        let secs_per_day = 86_400i64;
-        let mut day = self.epoch_days.0 + total_s.div_euclid(secs_per_day);
-        let mut sod = total_s.rem_euclid(secs_per_day); // 0..86399
+    /// The instant `secs` seconds and `ns` nanoseconds after 1970-01-01 00:00, counted on
+    /// the calendar's own scale: UTC, or wall-clock time with a local epoch.
+    fn instant_at(&self, secs: i64, ns: u32) -> Result<VInstant, CivilError> {
+        u64::try_from(secs - self.epoch_days.0 * 86_400)
+            .ok()
+            .and_then(|secs| secs.checked_mul(1_000_000_000))
+            .and_then(|n| n.checked_add(u64::from(ns)))
+            .map(VInstant)
+            .ok_or(CivilError::OutOfRange)
+    }
 
-        // correct for negatives (rare edge case)
-        if sod < 0 {
-            sod += secs_per_day;
-            day -= 1;
+    /// The instant of a UTC time given as seconds since the Unix epoch.
+    pub(crate) fn instant_from_unix(&self, unix_secs: i64, ns: u32) -> Result<VInstant, CivilError> {
+        match self.anchor {
+            Anchor::Utc => self.instant_at(unix_secs, ns),
+            Anchor::Local => {
+                let offset = match &self.tz {
+                    Some(tz) => tz.offset_at(unix_secs).utc_offset,
+                    None => self.tz_offset_secs,
+                };
+                self.instant_at(unix_secs + i64::from(offset), ns)
+            }
         }
+    }
 
-        let (year, month, dom) = civil_from_days(day);
-        let hour = (sod / 3600) as u32;
-        let min  = ((sod % 3600) / 60) as u32;
-        let sec  = (sod % 60) as u32;
-        let millis = (sub_ns / 1_000_000) as u32;
-        (year, month, dom, hour, min, sec, millis)
+    /// Convert a VInstant (ns) into (YYYY,MM,DD, hh,mm,ss, millis) in the selected timezone.
+    /// `to_civil_datetime` keeps the nanoseconds.
+    pub fn to_civil(&self, t: VInstant) -> (i32, u32, u32, u32, u32, u32, u32) {
+        let dt = self.to_civil_datetime(t);
+        let (date, time) = (dt.date(), dt.time());
+        (date.year(), date.month(), date.day(), time.hour(), time.minute(), time.second(), time.nanosecond() / 1_000_000)
+    }
+
+    /// The civil date and time `t` shows in the calendar's zone, to the nanosecond.
+    pub fn to_civil_datetime(&self, t: VInstant) -> CivilDateTime {
+        let mut secs = self.epoch_days.0 * 86_400 + (t.0 / 1_000_000_000) as i64;
+        if let Anchor::Utc = self.anchor {
+            secs += i64::from(self.utc_offset_at(t));
+        }
+        CivilDateTime::from_local(secs, (t.0 % 1_000_000_000) as u32)
     }
 
     /// Format as `YYYY-MM-DDTHH:MM:SS.mmm±HH:MM`
     pub fn format(&self, t: VInstant) -> SmallString {
-        use core::fmt::Write;
-        let (y, mo, d, h, mi, s, ms) = self.to_civil(t);
-
-        let sign = if self.tz_offset_secs >= 0 { '+' } else { '-' };
-        let off = self.tz_offset_secs.abs();
-        let off_h = (off / 3600) as u32;
-        let off_m = ((off % 3600) / 60) as u32;
-
         #[cfg(feature = "std")]
         let mut out: SmallString = SmallString::with_capacity(32);
     
         #[cfg(not(feature = "std"))]
         let mut out: SmallString = SmallString::new();
     
-        let _ = write!(
-            &mut out,
-            "{y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:02}.{ms:03}{sign}{off_h:02}:{off_m:02}"
-        );
+        let _ = write!(&mut out, "{}", self.display(t));
         out
     }
+
+    /// `t` as a civil date and time, for `{}` without allocating: same layout as
+    /// `format`. The precision sets the fractional second digits (default 3, at most 9),
+    /// so `{:.9}` shows nanoseconds and `{:.0}` whole seconds.
+    pub fn display(&self, t: VInstant) -> CalendarDisplay<'tz> {
+        CalendarDisplay { cal: *self, t }
+    }
+}
+
+/// `Display` adapter returned by `Calendar::display`.
+#[derive(Clone, Copy, Debug)]
+pub struct CalendarDisplay<'tz> {
+    cal: Calendar<'tz>,
+    t: VInstant,
+}
+
+impl fmt::Display for CalendarDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (y, mo, d, h, mi, s, _) = self.cal.to_civil(self.t);
+        let digits = f.precision().unwrap_or(3).min(9);
+
+        let offset = self.cal.utc_offset_at(self.t);
+        let sign = if offset >= 0 { '+' } else { '-' };
+        let off = offset.unsigned_abs();
+        let off_h = off / 3600;
+        let off_m = (off % 3600) / 60;
+
+        let mut buf = heapless::String::<48>::new();
+        write!(buf, "{y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:02}")?;
+        if digits > 0 {
+            // The offset is whole seconds, so the fraction is the instant's own.
+            let frac = self.t.0 % 1_000_000_000 / 10u64.pow(9 - digits as u32);
+            write!(buf, ".{frac:0digits$}")?;
+        }
+        write!(buf, "{sign}{off_h:02}:{off_m:02}")?;
+        pad(f, &buf)
+    }
 }
@@ -0,0 +1,189 @@
+// kairos-core/src/civil.rs
+//! Civil dates and times: what a wall clock and a proleptic Gregorian calendar show,
+//! with no zone attached. `Calendar::to_civil_datetime` and
+//! `Calendar::from_civil_datetime` convert between them and `VInstant`s.
+
+use crate::calendar::{civil_from_days, days_from_civil, days_in_month, is_leap, CivilDays, CivilError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// ISO 8601 numbering: Monday = 1 to Sunday = 7.
+    pub const fn number_from_monday(self) -> u32 {
+        self as u32 + 1
+    }
+
+    /// Sunday = 0 to Saturday = 6, like C's `tm_wday`.
+    pub const fn days_from_sunday(self) -> u32 {
+        (self as u32 + 1) % 7
+    }
+
+    /// The English name, e.g. `"Monday"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+}
+
+/// A date in the proleptic Gregorian calendar. Ordered chronologically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CivilDate {
+    pub(crate) year: i32,
+    pub(crate) month: u32,
+    pub(crate) day: u32,
+}
+
+impl CivilDate {
+    pub const MIN: CivilDate = CivilDate { year: i32::MIN, month: 1, day: 1 };
+    pub const MAX: CivilDate = CivilDate { year: i32::MAX, month: 12, day: 31 };
+
+    /// `CivilError::InvalidDateTime` unless `month` is 1–12 and `day` exists in it.
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, CivilError> {
+        if (1..=12).contains(&month) && (1..=days_in_month(i64::from(year), month)).contains(&day) {
+            Ok(CivilDate { year, month, day })
+        } else {
+            Err(CivilError::InvalidDateTime)
+        }
+    }
+
+    /// The date `days` after 1970-01-01, or `None` past `MIN`/`MAX`.
+    pub fn from_days(days: CivilDays) -> Option<Self> {
+        if (Self::MIN.to_days()..=Self::MAX.to_days()).contains(&days) {
+            let (year, month, day) = civil_from_days(days.0);
+            Some(CivilDate { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    pub fn to_days(self) -> CivilDays {
+        CivilDays(days_from_civil(self.year, self.month, self.day))
+    }
+
+    pub const fn year(self) -> i32 { self.year }
+    pub const fn month(self) -> u32 { self.month }
+    pub const fn day(self) -> u32 { self.day }
+
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday.
+        Weekday::ALL[(self.to_days().0 + 3).rem_euclid(7) as usize]
+    }
+
+    /// 1 for January 1st, up to 366 on December 31st of a leap year.
+    pub fn day_of_year(self) -> u32 {
+        (self.to_days().0 - days_from_civil(self.year, 1, 1)) as u32 + 1
+    }
+
+    /// The ISO 8601 week-based year and week number (1–53). Weeks start on Monday and
+    /// belong to the year their Thursday falls in, so early January can be in the
+    /// previous year's last week and late December in the next year's first.
+    pub fn iso_week(self) -> (i32, u32) {
+        let days = self.to_days().0;
+        let thursday = days + 4 - i64::from(self.weekday().number_from_monday());
+        let (year, _, _) = civil_from_days(thursday);
+        (year, ((thursday - days_from_civil(year, 1, 1)) / 7) as u32 + 1)
+    }
+
+    pub fn is_leap_year(self) -> bool {
+        is_leap(i64::from(self.year))
+    }
+
+    /// Days in this date's month (28–31).
+    pub fn days_in_month(self) -> u32 {
+        days_in_month(i64::from(self.year), self.month)
+    }
+
+    pub const fn and_time(self, time: CivilTime) -> CivilDateTime {
+        CivilDateTime { date: self, time }
+    }
+}
+
+/// A time of day with nanosecond precision. There are no leap seconds: `second` is at
+/// most 59.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CivilTime {
+    pub(crate) hour: u32,
+    pub(crate) minute: u32,
+    pub(crate) second: u32,
+    pub(crate) nanosecond: u32,
+}
+
+impl CivilTime {
+    pub const MIDNIGHT: CivilTime = CivilTime { hour: 0, minute: 0, second: 0, nanosecond: 0 };
+
+    /// `CivilError::InvalidDateTime` for hour 24, minute or second 60, or 10^9 ns.
+    pub fn new(hour: u32, minute: u32, second: u32, nanosecond: u32) -> Result<Self, CivilError> {
+        if hour < 24 && minute < 60 && second < 60 && nanosecond < 1_000_000_000 {
+            Ok(CivilTime { hour, minute, second, nanosecond })
+        } else {
+            Err(CivilError::InvalidDateTime)
+        }
+    }
+
+    pub const fn hour(self) -> u32 { self.hour }
+    pub const fn minute(self) -> u32 { self.minute }
+    pub const fn second(self) -> u32 { self.second }
+    pub const fn nanosecond(self) -> u32 { self.nanosecond }
+
+    /// Whole seconds since midnight.
+    pub const fn seconds_of_day(self) -> u32 {
+        self.hour * 3_600 + self.minute * 60 + self.second
+    }
+}
+
+/// A civil date and time of day, ordered chronologically.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CivilDateTime {
+    date: CivilDate,
+    time: CivilTime,
+}
+
+impl CivilDateTime {
+    pub const fn new(date: CivilDate, time: CivilTime) -> Self {
+        CivilDateTime { date, time }
+    }
+
+    /// `secs` seconds after 1970-01-01 00:00 on the wall clock, plus `nanosecond`.
+    pub(crate) fn from_local(secs: i64, nanosecond: u32) -> Self {
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        let sod = secs.rem_euclid(86_400) as u32;
+        CivilDateTime {
+            date: CivilDate { year, month, day },
+            time: CivilTime { hour: sod / 3_600, minute: sod / 60 % 60, second: sod % 60, nanosecond },
+        }
+    }
+
+    /// Whole seconds since 1970-01-01 00:00 on the wall clock.
+    pub(crate) fn local_secs(self) -> i64 {
+        self.date.to_days().0 * 86_400 + i64::from(self.time.seconds_of_day())
+    }
+
+    pub const fn date(self) -> CivilDate { self.date }
+    pub const fn time(self) -> CivilTime { self.time }
+}
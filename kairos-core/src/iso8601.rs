@@ -0,0 +1,320 @@
+// kairos-core/src/iso8601.rs
+//! Parsing RFC 3339 and ISO 8601 date-times into `VInstant`s (no allocation).
+//!
+//! `Calendar::parse_rfc3339` accepts exactly the RFC 3339 `date-time` profile, which
+//! is also what `Calendar::format` writes. `Calendar::parse_iso8601` also accepts the
+//! basic format (`20240315T103000Z`), ordinal dates (`2024-075`), week dates
+//! (`2024-W11-5`), expanded years (`+012024-03-15`), a comma before the fraction,
+//! `±hh` and `±hhmm` offsets, and leaves out the seconds, the time or the offset.
+//! Without an offset the time is read in the calendar's zone, like `from_civil`.
+
+use core::fmt;
+
+use crate::calendar::{days_from_civil, days_in_month, is_leap, Calendar, CivilError};
+use crate::civil::CivilDateTime;
+use crate::VInstant;
+
+/// Why a date-time string was rejected, and where.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseInstantError {
+    /// Byte offset of the problem in the input.
+    pub position: usize,
+    /// What was wrong.
+    pub kind: ParseInstantErrorKind,
+}
+
+/// The kinds of `ParseInstantError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseInstantErrorKind {
+    /// Something else was expected here, e.g. `"digit"` or `"':'"`.
+    Expected(&'static str),
+    /// The field starting here is out of range, e.g. `"month"` for month 13.
+    InvalidValue(&'static str),
+    /// A complete date-time is followed by more input.
+    TrailingCharacters,
+    /// The date-time is valid but names no single instant of the calendar. This is about
+    /// the whole input, so the position is always 0.
+    Unrepresentable(CivilError),
+}
+
+impl fmt::Display for ParseInstantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseInstantErrorKind::Expected(what) => write!(f, "expected {what} at byte {}", self.position),
+            ParseInstantErrorKind::InvalidValue(field) => write!(f, "invalid {field} at byte {}", self.position),
+            ParseInstantErrorKind::TrailingCharacters => write!(f, "unexpected characters at byte {}", self.position),
+            ParseInstantErrorKind::Unrepresentable(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseInstantError {}
+
+type Result<T> = core::result::Result<T, ParseInstantError>;
+
+/// A parsed date-time, before it is placed on a calendar.
+struct DateTime {
+    days: i64,
+    secs: u32,
+    nanos: u32,
+    offset: Option<i32>,
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+    /// RFC 3339 only: extended format, seconds and offset required.
+    strict: bool,
+}
+
+impl Parser<'_> {
+    fn err<T>(&self, kind: ParseInstantErrorKind) -> Result<T> {
+        Err(ParseInstantError { position: self.pos, kind })
+    }
+
+    fn invalid<T>(at: usize, field: &'static str) -> Result<T> {
+        Err(ParseInstantError { position: at, kind: ParseInstantErrorKind::InvalidValue(field) })
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        let hit = self.peek() == Some(c);
+        self.pos += usize::from(hit);
+        hit
+    }
+
+    fn expect(&mut self, c: u8, what: &'static str) -> Result<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            self.err(ParseInstantErrorKind::Expected(what))
+        }
+    }
+
+    /// Length of the run of digits at the cursor.
+    fn run(&self) -> usize {
+        self.s[self.pos..].iter().take_while(|c| c.is_ascii_digit()).count()
+    }
+
+    /// Exactly `n` digits.
+    fn digits(&mut self, n: usize) -> Result<u32> {
+        let mut v = 0u32;
+        for _ in 0..n {
+            match self.peek() {
+                Some(c) if c.is_ascii_digit() => v = v * 10 + u32::from(c - b'0'),
+                _ => return self.err(ParseInstantErrorKind::Expected("digit")),
+            }
+            self.pos += 1;
+        }
+        Ok(v)
+    }
+
+    fn date_time(&mut self) -> Result<DateTime> {
+        let days = self.date()?;
+        let mut dt = DateTime { days, secs: 0, nanos: 0, offset: None };
+        if self.peek().is_none() && !self.strict {
+            return Ok(dt);
+        }
+        if !matches!(self.peek(), Some(b'T' | b't' | b' ')) {
+            return self.err(ParseInstantErrorKind::Expected("'T'"));
+        }
+        self.pos += 1;
+        self.time(&mut dt)?;
+        dt.offset = self.offset()?;
+        if self.pos < self.s.len() {
+            return self.err(ParseInstantErrorKind::TrailingCharacters);
+        }
+        Ok(dt)
+    }
+
+    /// A calendar, ordinal or week date, as days since 1970-01-01.
+    fn date(&mut self) -> Result<i64> {
+        let year_at = self.pos;
+        let sign = match self.peek() {
+            Some(c @ (b'+' | b'-')) if !self.strict => {
+                self.pos += 1;
+                Some(c)
+            }
+            _ => None,
+        };
+        let year = match sign {
+            Some(sign) => {
+                let n = self.run();
+                if n < 4 {
+                    return self.err(ParseInstantErrorKind::Expected("digit"));
+                }
+                if n > 9 {
+                    return Self::invalid(year_at, "year");
+                }
+                let y = self.digits(n)? as i32;
+                if sign == b'-' { -y } else { y }
+            }
+            None => self.digits(4)? as i32,
+        };
+        let extended = self.eat(b'-');
+        // Expanded years need separators to tell the fields apart.
+        if !extended && (self.strict || sign.is_some()) {
+            return self.err(ParseInstantErrorKind::Expected("'-'"));
+        }
+        if !self.strict && self.eat(b'W') {
+            return self.week_date(year, extended);
+        }
+        let field_at = self.pos;
+        match (self.run(), self.strict) {
+            (3, false) => {
+                let ordinal = self.digits(3)?;
+                if ordinal == 0 || ordinal > 365 + u32::from(is_leap(i64::from(year))) {
+                    return Self::invalid(field_at, "day of year");
+                }
+                Ok(days_from_civil(year, 1, 1) + i64::from(ordinal) - 1)
+            }
+            _ => {
+                let month = self.digits(2)?;
+                if !(1..=12).contains(&month) {
+                    return Self::invalid(field_at, "month");
+                }
+                if extended {
+                    self.expect(b'-', "'-'")?;
+                }
+                let day_at = self.pos;
+                let day = self.digits(2)?;
+                if day == 0 || day > days_in_month(i64::from(year), month) {
+                    return Self::invalid(day_at, "day");
+                }
+                Ok(days_from_civil(year, month, day))
+            }
+        }
+    }
+
+    /// `Www[-D]` (or `WwwD`) after the year; the weekday defaults to Monday.
+    fn week_date(&mut self, year: i32, extended: bool) -> Result<i64> {
+        let week_at = self.pos;
+        let week = self.digits(2)?;
+        let monday = iso_week1_monday(year);
+        let weeks = (iso_week1_monday(year + 1) - monday) / 7;
+        if week == 0 || i64::from(week) > weeks {
+            return Self::invalid(week_at, "week");
+        }
+        let weekday_at = self.pos + usize::from(extended);
+        let has_weekday = if extended { self.s.get(self.pos) == Some(&b'-') } else { self.run() > 0 };
+        let weekday = if has_weekday {
+            self.pos = weekday_at;
+            self.digits(1)?
+        } else {
+            1
+        };
+        if !(1..=7).contains(&weekday) {
+            return Self::invalid(weekday_at, "weekday");
+        }
+        Ok(monday + 7 * (i64::from(week) - 1) + i64::from(weekday) - 1)
+    }
+
+    fn time(&mut self, dt: &mut DateTime) -> Result<()> {
+        let hour_at = self.pos;
+        let hour = self.digits(2)?;
+        let extended = self.eat(b':');
+        if !extended && self.strict {
+            return self.err(ParseInstantErrorKind::Expected("':'"));
+        }
+        let minute_at = self.pos;
+        let minute = self.digits(2)?;
+        let second_at = self.pos + usize::from(extended);
+        let has_seconds = if extended { self.peek() == Some(b':') } else { self.run() > 0 };
+        let second = if has_seconds {
+            self.pos = second_at;
+            self.digits(2)?
+        } else if self.strict {
+            return self.err(ParseInstantErrorKind::Expected("':'"));
+        } else {
+            0
+        };
+        // Leap seconds (`:60`) have no `VInstant`.
+        for (v, max, at, field) in [(hour, 23, hour_at, "hour"), (minute, 59, minute_at, "minute"), (second, 59, second_at, "second")] {
+            if v > max {
+                return Self::invalid(at, field);
+            }
+        }
+        dt.secs = hour * 3_600 + minute * 60 + second;
+        if has_seconds && (self.eat(b'.') || (!self.strict && self.eat(b','))) {
+            let n = self.run();
+            if n == 0 {
+                return self.err(ParseInstantErrorKind::Expected("digit"));
+            }
+            if n > 9 {
+                return Self::invalid(self.pos + 9, "fraction (nanoseconds at most)");
+            }
+            dt.nanos = self.digits(n)? * 10u32.pow(9 - n as u32);
+        }
+        Ok(())
+    }
+
+    /// `Z`, `±hh:mm`, or (ISO 8601) `±hhmm` / `±hh`.
+    fn offset(&mut self) -> Result<Option<i32>> {
+        let sign = match self.peek() {
+            Some(b'Z' | b'z') => {
+                self.pos += 1;
+                return Ok(Some(0));
+            }
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            None if !self.strict => return Ok(None),
+            _ => return self.err(ParseInstantErrorKind::Expected("offset")),
+        };
+        let at = self.pos;
+        self.pos += 1;
+        let hours = self.digits(2)?;
+        let minutes = if self.eat(b':') || (!self.strict && self.run() > 0) {
+            self.digits(2)?
+        } else if self.strict {
+            return self.err(ParseInstantErrorKind::Expected("':'"));
+        } else {
+            0
+        };
+        if hours > 23 || minutes > 59 {
+            return Self::invalid(at, "offset");
+        }
+        Ok(Some(sign * (hours * 3_600 + minutes * 60) as i32))
+    }
+}
+
+/// Days since 1970-01-01 of the Monday of ISO week 1, the week with January 4th.
+fn iso_week1_monday(year: i32) -> i64 {
+    let jan4 = days_from_civil(year, 1, 4);
+    // 1970-01-01 was a Thursday; Monday is 0 here.
+    jan4 - (jan4 + 3).rem_euclid(7)
+}
+
+impl Calendar<'_> {
+    /// Parse an RFC 3339 date-time such as `2024-03-15T10:30:00.25+01:00` (`T` may be
+    /// `t` or a space, `Z` may be `z`) into an instant of this calendar. Up to nine
+    /// fractional digits are accepted.
+    pub fn parse_rfc3339(&self, s: &str) -> core::result::Result<VInstant, ParseInstantError> {
+        self.parse_with(s, true)
+    }
+
+    /// Parse an ISO 8601 date or date-time: anything `parse_rfc3339` accepts, plus the
+    /// variants listed in the module docs. A date alone means midnight; a time without
+    /// an offset is read in the calendar's zone, which can make it ambiguous.
+    pub fn parse_iso8601(&self, s: &str) -> core::result::Result<VInstant, ParseInstantError> {
+        self.parse_with(s, false)
+    }
+
+    fn parse_with(&self, s: &str, strict: bool) -> Result<VInstant> {
+        let dt = Parser { s: s.as_bytes(), pos: 0, strict }.date_time()?;
+        let placed = match dt.offset {
+            Some(offset) => {
+                let unix = dt.days * 86_400 + i64::from(dt.secs) - i64::from(offset);
+                self.instant_from_unix(unix, dt.nanos)
+            }
+            None => {
+                let local = dt.days * 86_400 + i64::from(dt.secs);
+                self.from_civil_datetime(CivilDateTime::from_local(local, dt.nanos))
+            }
+        };
+        placed.map_err(|e| ParseInstantError { position: 0, kind: ParseInstantErrorKind::Unrepresentable(e) })
+    }
+}
@@ -27,18 +27,31 @@ pub mod autoclock;
 pub mod time;
 pub mod clock;
 pub mod calendar;
+pub mod civil;
+pub mod tz;
+pub mod iso8601;
+pub mod strftime;
 pub mod hlc;
+pub mod rng;
+#[cfg(feature = "serde")]
+mod serde_impls;
 
 // ── High-level re-exports ────────────────────────────────────────────────────
 pub use autoclock::AutoClock;
-pub use time::{VInstant, VDuration};
+pub use time::{VInstant, VDuration, VSignedDuration, ParseDurationError};
 pub use clock::Clock;
 pub use clock::manual::ManualClock;
 pub use clock::rate::RateClock;
 #[cfg(feature = "std")]
 pub use clock::std::StdClock;
 pub use hlc::{KairosHlc, KairosTs, KairosTs16};
-pub use calendar::Calendar;
+pub use rng::KairosRng;
+pub use calendar::{Calendar, CivilDays, CivilError};
+pub use civil::{CivilDate, CivilDateTime, CivilTime, Weekday};
+pub use iso8601::{ParseInstantError, ParseInstantErrorKind};
+pub use tz::{TimeZone, TzError, TzOffset};
+#[cfg(feature = "std")]
+pub use tz::ZoneFile;
 
 #[cfg(all(feature = "autoclock-systick", any(target_arch = "arm", target_arch = "aarch64")))]
 pub use autoclock::configure_systick;
@@ -0,0 +1,158 @@
+// kairos-core/src/rng.rs
+//! Seeded random numbers for simulations.
+//!
+//! Not cryptographically secure — `KairosRng` is meant for reproducible simulation noise
+//! such as jitter and packet loss. Give each node its own substream with `fork` so that
+//! adding or reordering nodes does not perturb the others.
+
+use crate::time::saturating_from_nanos_f64;
+use crate::VDuration;
+
+/// SplitMix64 step: used to expand seeds and derive substreams.
+#[inline(always)]
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Seeded, deterministic PRNG (xoshiro256**): `no_std`, allocation-free, and the same
+/// sequence on every platform for a given seed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KairosRng {
+    s: [u64; 4],
+}
+
+impl KairosRng {
+    /// Generator for `seed`; every seed (including 0) gives a valid, distinct stream.
+    pub fn seed_from_u64(seed: u64) -> Self {
+        let mut sm = seed;
+        Self { s: [splitmix64(&mut sm), splitmix64(&mut sm), splitmix64(&mut sm), splitmix64(&mut sm)] }
+    }
+
+    /// Independent substream number `stream`, derived from the current state without
+    /// advancing it: `fork(n)` is the same whatever other streams were forked before.
+    pub fn fork(&self, stream: u64) -> Self {
+        let mut id = stream;
+        let mut sm = self.s[0]
+            ^ self.s[1].rotate_left(17)
+            ^ self.s[2].rotate_left(31)
+            ^ self.s[3].rotate_left(47)
+            ^ splitmix64(&mut id);
+        Self { s: [splitmix64(&mut sm), splitmix64(&mut sm), splitmix64(&mut sm), splitmix64(&mut sm)] }
+    }
+
+    /// Next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+        result
+    }
+
+    /// Next 32 random bits.
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Uniform in `[0, 1)` with 53 bits of precision.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Uniform in `0..n`; returns 0 when `n` is 0. Unbiased (Lemire's method).
+    pub fn below(&mut self, n: u64) -> u64 {
+        if n == 0 {
+            return 0;
+        }
+        let mut m = u128::from(self.next_u64()) * u128::from(n);
+        if (m as u64) < n {
+            let threshold = n.wrapping_neg() % n;
+            while (m as u64) < threshold {
+                m = u128::from(self.next_u64()) * u128::from(n);
+            }
+        }
+        (m >> 64) as u64
+    }
+
+    /// `true` with probability `p` (clamped to `[0, 1]`); e.g. packet loss.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    /// Duration uniform in `[min, max]` (inclusive); `min` if the range is empty.
+    pub fn uniform(&mut self, min: VDuration, max: VDuration) -> VDuration {
+        if max.0 <= min.0 {
+            return min;
+        }
+        let spread = max.0 - min.0;
+        let offset = match spread.checked_add(1) {
+            Some(n) => self.below(n),
+            None => self.next_u64(),
+        };
+        VDuration(min.0 + offset)
+    }
+
+    /// Exponentially distributed duration with the given mean (e.g. Poisson arrivals).
+    pub fn exponential(&mut self, mean: VDuration) -> VDuration {
+        // 1 - u is in (0, 1], so the logarithm is finite.
+        let u = 1.0 - self.next_f64();
+        saturating_from_nanos_f64(-ln(u) * mean.0 as f64)
+    }
+
+    /// Approximately normal duration (Irwin–Hall sum of 12 uniforms), clamped at zero.
+    /// Samples never stray more than six standard deviations from the mean.
+    pub fn normal(&mut self, mean: VDuration, std_dev: VDuration) -> VDuration {
+        let mut sum = 0.0;
+        for _ in 0..12 {
+            sum += self.next_f64();
+        }
+        saturating_from_nanos_f64(mean.0 as f64 + (sum - 6.0) * std_dev.0 as f64)
+    }
+
+    /// `base` shifted by a uniform offset in `[-spread, +spread]`, saturating at zero.
+    pub fn jitter(&mut self, base: VDuration, spread: VDuration) -> VDuration {
+        let offset = self.uniform(VDuration(0), VDuration(spread.0.saturating_mul(2)));
+        VDuration(base.0.saturating_add(offset.0).saturating_sub(spread.0))
+    }
+}
+
+/// Natural logarithm for `x > 0`, without `std`/`libm`.
+/// Splits `x = m · 2^e` with `m` in `[√½, √2)` and sums the `atanh` series of `m`.
+fn ln(x: f64) -> f64 {
+    const LN_2: f64 = core::f64::consts::LN_2;
+    const SQRT_2: f64 = core::f64::consts::SQRT_2;
+    if x <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    let bits = x.to_bits();
+    let mut e = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let mut m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    if e == -1023 {
+        // Subnormal: renormalise first.
+        return ln(x * (1u64 << 54) as f64) - 54.0 * LN_2;
+    }
+    if m >= SQRT_2 {
+        m /= 2.0;
+        e += 1;
+    }
+    let z = (m - 1.0) / (m + 1.0);
+    let z2 = z * z;
+    let mut term = z;
+    let mut sum = 0.0;
+    let mut k = 1.0;
+    // |z| < 0.172: 12 terms are below f64 precision.
+    for _ in 0..12 {
+        sum += term / k;
+        term *= z2;
+        k += 2.0;
+    }
+    2.0 * sum + e as f64 * LN_2
+}
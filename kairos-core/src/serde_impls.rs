@@ -0,0 +1,519 @@
+// kairos-core/src/serde_impls.rs
+//! `serde` support (feature `serde`).
+//!
+//! Human-readable formats (JSON, YAML, …) get strings: instants as RFC 3339 in UTC
+//! (the default `Calendar`), durations with a unit suffix such as `"250ms"`. Compact
+//! formats (postcard, bincode, …) get plain integers. In human-readable formats the
+//! integer forms are accepted on input as well.
+
+use core::fmt::{self, Write};
+
+use serde::de::{self, Deserialize, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Unexpected, Visitor};
+use serde::ser::{self, Serialize, SerializeStruct, SerializeTuple, Serializer};
+
+use crate::calendar::{civil_from_days, days_from_civil, days_in_month, Anchor, CivilDays};
+use crate::{Calendar, KairosTs, KairosTs16, VDuration, VInstant, VSignedDuration};
+
+/// Scratch buffer for the string forms; the longest is an RFC 3339 instant (30 bytes).
+type Buf = heapless::String<48>;
+
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Duration units, largest first.
+const UNITS: [(&str, u64); 7] = [
+    ("d", 86_400 * NANOS_PER_SEC),
+    ("h", 3_600 * NANOS_PER_SEC),
+    ("m", 60 * NANOS_PER_SEC),
+    ("s", NANOS_PER_SEC),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+/// `d` in the largest unit that holds it exactly, e.g. `90s`, `1500ms`. Stricter than
+/// `Display` so that the output stays one integer and one unit; reading it back goes
+/// through `FromStr`, which accepts both.
+fn write_duration(out: &mut impl Write, d: VDuration) -> fmt::Result {
+    if d.0 == 0 {
+        return out.write_str("0s");
+    }
+    let (unit, scale) = UNITS.iter().find(|(_, scale)| d.0.is_multiple_of(*scale)).copied().unwrap_or(("ns", 1));
+    write!(out, "{}{}", d.0 / scale, unit)
+}
+
+/// `YYYY-MM-DD`, with a sign and at least four digits for the year.
+fn write_date(out: &mut impl Write, days: i64) -> fmt::Result {
+    let (y, m, d) = civil_from_days(days);
+    if y < 0 {
+        out.write_char('-')?;
+    }
+    write!(out, "{:04}-{m:02}-{d:02}", y.unsigned_abs())
+}
+
+/// `t` as RFC 3339 in UTC, with 0, 3, 6 or 9 fractional digits as needed.
+fn write_rfc3339(out: &mut impl Write, t: VInstant) -> fmt::Result {
+    let secs = t.0 / NANOS_PER_SEC;
+    let nanos = (t.0 % NANOS_PER_SEC) as u32;
+    write_date(out, (secs / 86_400) as i64)?;
+    let sod = secs % 86_400;
+    write!(out, "T{:02}:{:02}:{:02}", sod / 3600, sod / 60 % 60, sod % 60)?;
+    if nanos == 0 {
+    } else if nanos.is_multiple_of(1_000_000) {
+        write!(out, ".{:03}", nanos / 1_000_000)?;
+    } else if nanos.is_multiple_of(1_000) {
+        write!(out, ".{:06}", nanos / 1_000)?;
+    } else {
+        write!(out, ".{nanos:09}")?;
+    }
+    out.write_char('Z')
+}
+
+/// Fixed-width decimal field.
+fn digits(b: &[u8]) -> Option<u32> {
+    if b.is_empty() || !b.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    Some(b.iter().fold(0, |n, &c| n * 10 + u32::from(c - b'0')))
+}
+
+/// `YYYY-MM-DD` (optionally signed, four or more year digits) → days since 1970-01-01.
+fn parse_date(s: &[u8]) -> Option<i64> {
+    let (neg, s) = match s.first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let dash = s.len().checked_sub(6)?;
+    if !(4..=9).contains(&dash) || s[dash] != b'-' || s[dash + 3] != b'-' {
+        return None;
+    }
+    let y = i64::from(digits(&s[..dash])?);
+    let y = if neg { -y } else { y };
+    let (m, d) = (digits(&s[dash + 1..dash + 3])?, digits(&s[dash + 4..])?);
+    if !(1..=12).contains(&m) || d == 0 || d > days_in_month(y, m) {
+        return None;
+    }
+    Some(days_from_civil(i32::try_from(y).ok()?, m, d))
+}
+
+/// `±HH:MM` or `±HH:MM:SS` → seconds east of UTC.
+fn parse_offset(s: &[u8]) -> Option<i32> {
+    let sign = match s.first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let (h, m, sec) = match s.len() {
+        6 if s[3] == b':' => (digits(&s[1..3])?, digits(&s[4..6])?, 0),
+        9 if s[3] == b':' && s[6] == b':' => (digits(&s[1..3])?, digits(&s[4..6])?, digits(&s[7..9])?),
+        _ => return None,
+    };
+    if h > 23 || m > 59 || sec > 59 {
+        return None;
+    }
+    Some(sign * (h * 3600 + m * 60 + sec) as i32)
+}
+
+fn write_offset(out: &mut impl Write, secs: i32) -> fmt::Result {
+    let sign = if secs < 0 { '-' } else { '+' };
+    let abs = secs.unsigned_abs();
+    write!(out, "{sign}{:02}:{:02}", abs / 3600, abs / 60 % 60)?;
+    if !abs.is_multiple_of(60) {
+        write!(out, ":{:02}", abs % 60)?;
+    }
+    Ok(())
+}
+
+/// Format into the scratch buffer, mapping overflow to a serializer error.
+fn to_buf<E: ser::Error>(f: impl FnOnce(&mut Buf) -> fmt::Result) -> Result<Buf, E> {
+    let mut buf = Buf::new();
+    f(&mut buf).map_err(|_| E::custom("formatted value too long"))?;
+    Ok(buf)
+}
+
+/// Accepts nanoseconds as an integer, or the string form parsed by `parse`.
+struct NanosVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Option<T>,
+    wrap: fn(u64) -> T,
+}
+
+impl<T> Visitor<'_> for NanosVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+        Ok((self.wrap)(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+        u64::try_from(v).map(self.wrap).map_err(|_| E::invalid_value(Unexpected::Signed(v), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        (self.parse)(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+impl Serialize for VDuration {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.serialize_str(&to_buf(|b| write_duration(b, *self))?)
+        } else {
+            s.serialize_u64(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VDuration {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = NanosVisitor {
+            expecting: "a duration in nanoseconds or a string such as \"250ms\"",
+            parse: |s| s.parse().ok(),
+            wrap: VDuration,
+        };
+        if d.is_human_readable() {
+            d.deserialize_any(v)
+        } else {
+            d.deserialize_u64(v)
+        }
+    }
+}
+
+impl Serialize for VSignedDuration {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.serialize_str(&to_buf(|b| {
+                if self.is_negative() {
+                    b.write_char('-')?;
+                }
+                write_duration(b, self.abs())
+            })?)
+        } else {
+            s.serialize_i64(self.0)
+        }
+    }
+}
+
+struct SignedVisitor;
+
+impl Visitor<'_> for SignedVisitor {
+    type Value = VSignedDuration;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("signed nanoseconds or a string such as \"-250ms\"")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<VSignedDuration, E> {
+        Ok(VSignedDuration(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<VSignedDuration, E> {
+        i64::try_from(v).map(VSignedDuration).map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<VSignedDuration, E> {
+        v.parse().map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+impl<'de> Deserialize<'de> for VSignedDuration {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            d.deserialize_any(SignedVisitor)
+        } else {
+            d.deserialize_i64(SignedVisitor)
+        }
+    }
+}
+
+impl Serialize for VInstant {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            s.serialize_str(&to_buf(|b| write_rfc3339(b, *self))?)
+        } else {
+            s.serialize_u64(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VInstant {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = NanosVisitor {
+            expecting: "nanoseconds since the epoch or an RFC 3339 timestamp",
+            parse: |s| Calendar::default().parse_rfc3339(s).ok(),
+            wrap: VInstant,
+        };
+        if d.is_human_readable() {
+            d.deserialize_any(v)
+        } else {
+            d.deserialize_u64(v)
+        }
+    }
+}
+
+/// Deserializes a struct field name into its index in `names`.
+struct FieldSeed(&'static [&'static str]);
+
+impl<'de> DeserializeSeed<'de> for FieldSeed {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<usize, D::Error> {
+        d.deserialize_identifier(self)
+    }
+}
+
+impl Visitor<'_> for FieldSeed {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a field name")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<usize, E> {
+        usize::try_from(v)
+            .ok()
+            .filter(|&i| i < self.0.len())
+            .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<usize, E> {
+        self.0.iter().position(|&name| name == v).ok_or_else(|| E::unknown_field(v, self.0))
+    }
+}
+
+const TS_FIELDS: &[&str] = &["phys", "log", "node"];
+
+impl Serialize for KairosTs {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if s.is_human_readable() {
+            let mut st = s.serialize_struct("KairosTs", 3)?;
+            st.serialize_field("phys", &VInstant(self.phys_ns))?;
+            st.serialize_field("log", &self.log)?;
+            st.serialize_field("node", &self.node)?;
+            st.end()
+        } else {
+            let mut t = s.serialize_tuple(3)?;
+            t.serialize_element(&self.phys_ns)?;
+            t.serialize_element(&self.log)?;
+            t.serialize_element(&self.node)?;
+            t.end()
+        }
+    }
+}
+
+struct TsVisitor;
+
+impl<'de> Visitor<'de> for TsVisitor {
+    type Value = KairosTs;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an HLC timestamp (phys, log, node)")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<KairosTs, A::Error> {
+        let phys: VInstant = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let log = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let node = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        Ok(KairosTs { phys_ns: phys.0, log, node })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<KairosTs, A::Error> {
+        let (mut phys, mut log, mut node) = (None::<VInstant>, None, None);
+        while let Some(i) = map.next_key_seed(FieldSeed(TS_FIELDS))? {
+            match i {
+                0 if phys.is_none() => phys = Some(map.next_value()?),
+                1 if log.is_none() => log = Some(map.next_value()?),
+                2 if node.is_none() => node = Some(map.next_value()?),
+                _ => return Err(de::Error::duplicate_field(TS_FIELDS[i])),
+            }
+        }
+        Ok(KairosTs {
+            phys_ns: phys.ok_or_else(|| de::Error::missing_field("phys"))?.0,
+            log: log.ok_or_else(|| de::Error::missing_field("log"))?,
+            node: node.ok_or_else(|| de::Error::missing_field("node"))?,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for KairosTs {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            d.deserialize_struct("KairosTs", TS_FIELDS, TsVisitor)
+        } else {
+            d.deserialize_tuple(3, TsVisitor)
+        }
+    }
+}
+
+impl Serialize for KairosTs16 {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        KairosTs { phys_ns: self.phys_ns, log: self.log, node: self.node }.serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for KairosTs16 {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        KairosTs::deserialize(d).map(KairosTs16::from)
+    }
+}
+
+const CALENDAR_FIELDS: &[&str] = &["epoch", "tz_offset", "anchor"];
+const MAX_EPOCH_DAYS: u64 = 365 * i32::MAX as u64;
+
+/// Human-readable calendar fields, each a string.
+struct DateStr(i64);
+struct OffsetStr(i32);
+struct AnchorStr(Anchor);
+
+impl Serialize for DateStr {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&to_buf(|b| write_date(b, self.0))?)
+    }
+}
+
+impl Serialize for OffsetStr {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(&to_buf(|b| write_offset(b, self.0))?)
+    }
+}
+
+impl Serialize for AnchorStr {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(match self.0 {
+            Anchor::Utc => "utc",
+            Anchor::Local => "local",
+        })
+    }
+}
+
+/// Parses a string field of the human-readable calendar.
+struct StrVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&str) -> Option<T>,
+}
+
+impl<T> Visitor<'_> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        (self.parse)(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+macro_rules! str_field {
+    ($ty:ident, $expecting:literal, $parse:expr) => {
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+                d.deserialize_str(StrVisitor { expecting: $expecting, parse: $parse })
+            }
+        }
+    };
+}
+
+str_field!(DateStr, "a date such as \"1970-01-01\"", |s| parse_date(s.as_bytes()).map(DateStr));
+str_field!(OffsetStr, "a UTC offset such as \"-03:00\"", |s| parse_offset(s.as_bytes()).map(OffsetStr));
+str_field!(AnchorStr, "\"utc\" or \"local\"", |s| match s {
+    "utc" => Some(AnchorStr(Anchor::Utc)),
+    "local" => Some(AnchorStr(Anchor::Local)),
+    _ => None,
+});
+
+/// A `TimeZone` is borrowed zone data with no name to write, so calendars that use
+/// one are rejected rather than silently saved with their fixed offset.
+impl Serialize for Calendar<'_> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if self.tz.is_some() {
+            return Err(ser::Error::custom("cannot serialize a Calendar with a TimeZone"));
+        }
+        if s.is_human_readable() {
+            let mut st = s.serialize_struct("Calendar", 3)?;
+            st.serialize_field("epoch", &DateStr(self.epoch_days.0))?;
+            st.serialize_field("tz_offset", &OffsetStr(self.tz_offset_secs))?;
+            st.serialize_field("anchor", &AnchorStr(self.anchor))?;
+            st.end()
+        } else {
+            let mut t = s.serialize_tuple(3)?;
+            t.serialize_element(&self.epoch_days.0)?;
+            t.serialize_element(&self.tz_offset_secs)?;
+            t.serialize_element(&(self.anchor == Anchor::Local))?;
+            t.end()
+        }
+    }
+}
+
+/// Compact calendar: epoch day number, offset seconds, whether the epoch is local.
+struct CalendarVisitor {
+    readable: bool,
+}
+
+impl CalendarVisitor {
+    fn build<E: de::Error>(days: i64, offset: i32, anchor: Anchor) -> Result<Calendar<'static>, E> {
+        // Keep the epoch within the years `Calendar::with_epoch` can express.
+        if days.unsigned_abs() > MAX_EPOCH_DAYS {
+            return Err(E::invalid_value(Unexpected::Signed(days), &"an epoch day within the i32 year range"));
+        }
+        if offset.unsigned_abs() >= 86_400 {
+            return Err(E::invalid_value(Unexpected::Signed(offset.into()), &"a UTC offset under 24 hours"));
+        }
+        Ok(Calendar { epoch_days: CivilDays(days), tz_offset_secs: offset, anchor, tz: None })
+    }
+}
+
+impl<'de> Visitor<'de> for CalendarVisitor {
+    type Value = Calendar<'static>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a calendar (epoch, tz_offset, anchor)")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Calendar<'static>, A::Error> {
+        let missing = |i| de::Error::invalid_length(i, &"a calendar (epoch, tz_offset, anchor)");
+        if self.readable {
+            let days: DateStr = seq.next_element()?.ok_or_else(|| missing(0))?;
+            let offset: OffsetStr = seq.next_element()?.ok_or_else(|| missing(1))?;
+            let anchor: AnchorStr = seq.next_element()?.ok_or_else(|| missing(2))?;
+            Self::build(days.0, offset.0, anchor.0)
+        } else {
+            let days: i64 = seq.next_element()?.ok_or_else(|| missing(0))?;
+            let offset: i32 = seq.next_element()?.ok_or_else(|| missing(1))?;
+            let local: bool = seq.next_element()?.ok_or_else(|| missing(2))?;
+            Self::build(days, offset, if local { Anchor::Local } else { Anchor::Utc })
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Calendar<'static>, A::Error> {
+        let (mut epoch, mut offset, mut anchor) = (None::<DateStr>, None::<OffsetStr>, None::<AnchorStr>);
+        while let Some(i) = map.next_key_seed(FieldSeed(CALENDAR_FIELDS))? {
+            match i {
+                0 if epoch.is_none() => epoch = Some(map.next_value()?),
+                1 if offset.is_none() => offset = Some(map.next_value()?),
+                2 if anchor.is_none() => anchor = Some(map.next_value()?),
+                _ => return Err(de::Error::duplicate_field(CALENDAR_FIELDS[i])),
+            }
+        }
+        let defaults = Calendar::default();
+        Self::build(
+            epoch.map_or(defaults.epoch_days.0, |e| e.0),
+            offset.map_or(defaults.tz_offset_secs, |o| o.0),
+            anchor.map_or(defaults.anchor, |a| a.0),
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for Calendar<'_> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if d.is_human_readable() {
+            d.deserialize_struct("Calendar", CALENDAR_FIELDS, CalendarVisitor { readable: true })
+        } else {
+            d.deserialize_tuple(3, CalendarVisitor { readable: false })
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// kairos-core/src/strftime.rs
+//! strftime-style formatting of `VInstant`s through a `Calendar` (no allocation).
+//!
+//! `Calendar::format_to` writes straight into any `core::fmt::Write`, and
+//! `Calendar::strftime` wraps the same thing as a `Display` value, so the output is
+//! never truncated to a fixed buffer. Names are English and the layouts of `%c`, `%x`
+//! and `%X` are those of the C locale.
+//!
+//! | Spec | Meaning | Example |
+//! |------|---------|---------|
+//! | `%Y` `%C` `%y` | year (at least 4 digits), century, year % 100 | `2024` `20` `24` |
+//! | `%m` `%b`/`%h` `%B` | month, abbreviated and full month name | `03` `Mar` `March` |
+//! | `%d` `%e` | day of month, zero- and space-padded | `05` ` 5` |
+//! | `%j` | day of year | `065` |
+//! | `%a` `%A` | abbreviated and full weekday name | `Tue` `Tuesday` |
+//! | `%u` `%w` | weekday, Monday = 1 to 7 and Sunday = 0 to 6 | `2` `2` |
+//! | `%G` `%g` `%V` | ISO 8601 week-based year, its last two digits, week | `2024` `24` `10` |
+//! | `%U` `%W` | week of the year, starting on Sunday and on Monday | `09` `10` |
+//! | `%H` `%k` `%I` `%l` | hour 00–23, space-padded, 01–12, space-padded | `07` ` 7` `07` ` 7` |
+//! | `%p` `%P` | `AM`/`PM`, `am`/`pm` | `AM` `am` |
+//! | `%M` `%S` | minute, second | `30` `00` |
+//! | `%f` | nanoseconds, 9 digits | `250000000` |
+//! | `%.f` | `.` and 3, 6 or 9 digits as needed, nothing for whole seconds | `.250` |
+//! | `%.3f` `%3f` | fraction to 1–9 digits, with or without the `.` | `.250` `250` |
+//! | `%z` `%:z` | UTC offset | `+0100` `+01:00` |
+//! | `%Z` | zone abbreviation, or the offset without a `TimeZone` | `CET` `+01:00` |
+//! | `%s` | seconds since 1970-01-01 00:00 UTC | `1709620200` |
+//! | `%F` `%T` `%D` `%R` | `%Y-%m-%d`, `%H:%M:%S`, `%m/%d/%y`, `%H:%M` | |
+//! | `%c` `%x` `%X` | `%a %b %e %H:%M:%S %Y`, `%m/%d/%y`, `%H:%M:%S` | |
+//! | `%+` | `%Y-%m-%dT%H:%M:%S%.f%:z` | |
+//! | `%n` `%t` `%%` | newline, tab, `%` | |
+//!
+//! Numeric fields take a padding flag after the `%`: `-` for none, `_` for spaces and
+//! `0` for zeros, e.g. `%-d` or `%_m`. A conversion that is not in the table is written
+//! out as it appears in the pattern.
+
+use core::fmt::{self, Write};
+
+use crate::calendar::{Anchor, Calendar};
+use crate::civil::{CivilDate, CivilDateTime, CivilTime};
+use crate::VInstant;
+
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+#[derive(Clone, Copy)]
+enum Pad {
+    Zero,
+    Space,
+    None,
+}
+
+fn num<W: Write + ?Sized>(out: &mut W, v: i64, width: usize, pad: Pad) -> fmt::Result {
+    match pad {
+        Pad::Zero => write!(out, "{v:0width$}"),
+        Pad::Space => write!(out, "{v:width$}"),
+        Pad::None => write!(out, "{v}"),
+    }
+}
+
+/// Everything a pattern can ask for, worked out once per instant.
+struct Fields<'a> {
+    date: CivilDate,
+    time: CivilTime,
+    offset: i32,
+    zone: Option<&'a str>,
+    unix: i64,
+}
+
+impl Fields<'_> {
+    fn new<'tz>(cal: &Calendar<'tz>, t: VInstant) -> Fields<'tz> {
+        let (offset, zone) = cal.zone_at(t);
+        let secs = cal.epoch_days.0 * 86_400 + (t.0 / 1_000_000_000) as i64;
+        let (local, unix) = match cal.anchor {
+            Anchor::Utc => (secs + i64::from(offset), secs),
+            Anchor::Local => (secs, secs - i64::from(offset)),
+        };
+        let dt = CivilDateTime::from_local(local, (t.0 % 1_000_000_000) as u32);
+        Fields { date: dt.date(), time: dt.time(), offset, zone, unix }
+    }
+
+    fn write<W: Write + ?Sized>(&self, out: &mut W, pattern: &str) -> fmt::Result {
+        let mut rest = pattern;
+        while let Some(at) = rest.find('%') {
+            out.write_str(&rest[..at])?;
+            rest = &rest[at..];
+            let used = self.conversion(out, rest)?;
+            rest = &rest[used..];
+        }
+        out.write_str(rest)
+    }
+
+    /// Write the conversion `spec` starts with; returns how many bytes it took.
+    fn conversion<W: Write + ?Sized>(&self, out: &mut W, spec: &str) -> Result<usize, fmt::Error> {
+        let b = spec.as_bytes();
+        let mut i = 1;
+        let pad = match b.get(i) {
+            Some(b'-') => Some(Pad::None),
+            Some(b'_') => Some(Pad::Space),
+            Some(b'0') => Some(Pad::Zero),
+            _ => None,
+        };
+        i += usize::from(pad.is_some());
+        let colon = b.get(i) == Some(&b':');
+        i += usize::from(colon);
+        let dot = b.get(i) == Some(&b'.');
+        i += usize::from(dot);
+        let digits = match b.get(i) {
+            Some(&c @ b'1'..=b'9') => Some(u32::from(c - b'0')),
+            _ => None,
+        };
+        i += usize::from(digits.is_some());
+        let Some(&c) = b.get(i) else {
+            out.write_str(spec)?;
+            return Ok(spec.len());
+        };
+
+        let n = |out: &mut W, v: i64, width: usize| num(out, v, width, pad.unwrap_or(Pad::Zero));
+        let s = |out: &mut W, v: i64, width: usize| num(out, v, width, pad.unwrap_or(Pad::Space));
+        let (date, time) = (self.date, self.time);
+        let hour = i64::from(time.hour());
+        let weekday = date.weekday();
+        let ordinal = i64::from(date.day_of_year());
+        let plain = !colon && !dot && digits.is_none();
+        match c {
+            b'f' if !colon => self.fraction(out, dot, digits)?,
+            b'z' if !dot && digits.is_none() => self.offset(out, colon)?,
+            // Anything else takes at most a padding flag.
+            _ if !plain => {
+                out.write_str(&spec[..i])?;
+                return Ok(i);
+            }
+            b'Y' => n(out, i64::from(date.year()), 4)?,
+            b'C' => n(out, i64::from(date.year()).div_euclid(100), 2)?,
+            b'y' => n(out, i64::from(date.year()).rem_euclid(100), 2)?,
+            b'm' => n(out, i64::from(date.month()), 2)?,
+            b'b' | b'h' => out.write_str(&MONTHS[date.month() as usize - 1][..3])?,
+            b'B' => out.write_str(MONTHS[date.month() as usize - 1])?,
+            b'd' => n(out, i64::from(date.day()), 2)?,
+            b'e' => s(out, i64::from(date.day()), 2)?,
+            b'j' => n(out, ordinal, 3)?,
+            b'a' => out.write_str(&weekday.name()[..3])?,
+            b'A' => out.write_str(weekday.name())?,
+            b'u' => n(out, i64::from(weekday.number_from_monday()), 1)?,
+            b'w' => n(out, i64::from(weekday.days_from_sunday()), 1)?,
+            b'G' => n(out, i64::from(date.iso_week().0), 4)?,
+            b'g' => n(out, i64::from(date.iso_week().0).rem_euclid(100), 2)?,
+            b'V' => n(out, i64::from(date.iso_week().1), 2)?,
+            b'U' => n(out, (ordinal + 6 - i64::from(weekday.days_from_sunday())) / 7, 2)?,
+            b'W' => n(out, (ordinal + 7 - i64::from(weekday.number_from_monday())) / 7, 2)?,
+            b'H' => n(out, hour, 2)?,
+            b'k' => s(out, hour, 2)?,
+            b'I' => n(out, (hour + 11) % 12 + 1, 2)?,
+            b'l' => s(out, (hour + 11) % 12 + 1, 2)?,
+            b'p' => out.write_str(if hour < 12 { "AM" } else { "PM" })?,
+            b'P' => out.write_str(if hour < 12 { "am" } else { "pm" })?,
+            b'M' => n(out, i64::from(time.minute()), 2)?,
+            b'S' => n(out, i64::from(time.second()), 2)?,
+            b'Z' => match self.zone {
+                Some(zone) => out.write_str(zone)?,
+                None => self.offset(out, true)?,
+            },
+            b's' => num(out, self.unix, 1, pad.unwrap_or(Pad::None))?,
+            b'F' => self.write(out, "%Y-%m-%d")?,
+            b'T' | b'X' => self.write(out, "%H:%M:%S")?,
+            b'D' | b'x' => self.write(out, "%m/%d/%y")?,
+            b'R' => self.write(out, "%H:%M")?,
+            b'c' => self.write(out, "%a %b %e %H:%M:%S %Y")?,
+            b'+' => self.write(out, "%Y-%m-%dT%H:%M:%S%.f%:z")?,
+            b'n' => out.write_char('\n')?,
+            b't' => out.write_char('\t')?,
+            b'%' => out.write_char('%')?,
+            _ => {
+                // Leave `c` itself to the caller: it may not be a whole character.
+                out.write_str(&spec[..i])?;
+                return Ok(i);
+            }
+        }
+        Ok(i + 1)
+    }
+
+    /// `%f`, `%.f`, `%Nf` and `%.Nf`.
+    fn fraction<W: Write + ?Sized>(&self, out: &mut W, dot: bool, digits: Option<u32>) -> fmt::Result {
+        let digits = match digits {
+            Some(d) => d,
+            None if !dot => 9,
+            None if self.time.nanosecond() == 0 => return Ok(()),
+            None if self.time.nanosecond().is_multiple_of(1_000_000) => 3,
+            None if self.time.nanosecond().is_multiple_of(1_000) => 6,
+            None => 9,
+        };
+        if dot {
+            out.write_char('.')?;
+        }
+        let frac = self.time.nanosecond() / 10u32.pow(9 - digits);
+        write!(out, "{frac:0width$}", width = digits as usize)
+    }
+
+    /// `±hhmm`, or `±hh:mm` with `colon`.
+    fn offset<W: Write + ?Sized>(&self, out: &mut W, colon: bool) -> fmt::Result {
+        let sign = if self.offset >= 0 { '+' } else { '-' };
+        let off = self.offset.unsigned_abs();
+        let sep = if colon { ":" } else { "" };
+        write!(out, "{sign}{:02}{sep}{:02}", off / 3_600, off % 3_600 / 60)
+    }
+}
+
+impl<'tz> Calendar<'tz> {
+    /// Write `t` to `out` following a strftime-style `pattern`, e.g.
+    /// `"%Y-%m-%d %H:%M:%S%.9f %z"`; see the module docs for the conversions. Fails only
+    /// if `out` does.
+    pub fn format_to<W: Write + ?Sized>(&self, out: &mut W, t: VInstant, pattern: &str) -> fmt::Result {
+        Fields::new(self, t).write(out, pattern)
+    }
+
+    /// `t` formatted with `pattern` as a `Display` value, for `write!` and `{}`.
+    pub fn strftime<'p>(&self, t: VInstant, pattern: &'p str) -> Strftime<'tz, 'p> {
+        Strftime { cal: *self, t, pattern }
+    }
+}
+
+/// `Display` adapter returned by `Calendar::strftime`.
+#[derive(Clone, Copy, Debug)]
+pub struct Strftime<'tz, 'p> {
+    cal: Calendar<'tz>,
+    t: VInstant,
+    pattern: &'p str,
+}
+
+impl fmt::Display for Strftime<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cal.format_to(f, self.t, self.pattern)
+    }
+}
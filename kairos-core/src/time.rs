@@ -1,22 +1,91 @@
 #![allow(clippy::manual_saturating_arithmetic)]
 
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
 /// Monotonic virtual timestamp in **nanoseconds** since an arbitrary epoch.
 /// Arithmetic is **wrapping** for deltas across the epoch boundary.
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct VInstant(pub u64);
 
 impl VInstant {
     /// Get the timestamp in nanoseconds (identity).
     #[inline(always)]
     pub const fn as_nanos(self) -> u64 { self.0 }
+
+    /// Signed time from `earlier` to `self`: negative if `earlier` is actually later.
+    /// Saturates at `VSignedDuration::MIN`/`MAX` for gaps over ~292 years.
+    #[inline(always)]
+    pub const fn signed_duration_since(self, earlier: VInstant) -> VSignedDuration {
+        let diff = self.0 as i128 - earlier.0 as i128;
+        if diff > i64::MAX as i128 {
+            VSignedDuration::MAX
+        } else if diff < i64::MIN as i128 {
+            VSignedDuration::MIN
+        } else {
+            VSignedDuration(diff as i64)
+        }
+    }
+
+    /// Time from `earlier` to `self`, or `None` if `earlier` is later.
+    #[inline(always)]
+    pub const fn checked_duration_since(self, earlier: VInstant) -> Option<VDuration> {
+        match self.0.checked_sub(earlier.0) { Some(ns) => Some(VDuration(ns)), None => None }
+    }
+
+    /// Time from `earlier` to `self`, or `ZERO` if `earlier` is later.
+    #[inline(always)]
+    pub const fn saturating_duration_since(self, earlier: VInstant) -> VDuration {
+        VDuration(self.0.saturating_sub(earlier.0))
+    }
+
+    /// `self + d`, or `None` past the end of time.
+    #[inline(always)]
+    pub const fn checked_add(self, d: VDuration) -> Option<VInstant> {
+        match self.0.checked_add(d.0) { Some(ns) => Some(VInstant(ns)), None => None }
+    }
+
+    /// `self - d`, or `None` before the epoch.
+    #[inline(always)]
+    pub const fn checked_sub(self, d: VDuration) -> Option<VInstant> {
+        match self.0.checked_sub(d.0) { Some(ns) => Some(VInstant(ns)), None => None }
+    }
+
+    /// `self` moved by a signed duration, or `None` outside `VInstant`'s range.
+    #[inline(always)]
+    pub const fn checked_add_signed(self, d: VSignedDuration) -> Option<VInstant> {
+        match self.0.checked_add_signed(d.0) { Some(ns) => Some(VInstant(ns)), None => None }
+    }
 }
 
 /// Virtual duration in **nanoseconds**.
 /// Construction helpers are **saturating** to avoid overflow on large inputs.
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct VDuration(pub u64);
 
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+const NANOS_PER_MILLI: u64 = 1_000_000;
+const NANOS_PER_MICRO: u64 = 1_000;
+
+/// Nanoseconds from a float, rounded to nearest and saturating at both ends (NaN → 0).
+#[inline(always)]
+pub(crate) fn saturating_from_nanos_f64(ns: f64) -> VDuration {
+    if ns.is_nan() || ns <= 0.0 {
+        VDuration::ZERO
+    } else if ns >= u64::MAX as f64 {
+        VDuration::MAX
+    } else {
+        VDuration((ns + 0.5) as u64)
+    }
+}
+
 impl VDuration {
+    /// Zero-length duration.
+    pub const ZERO: VDuration = VDuration(0);
+
+    /// Longest representable duration (about 584 years).
+    pub const MAX: VDuration = VDuration(u64::MAX);
+
     /// Create from whole seconds (saturating).
     #[inline(always)]
     pub const fn from_secs(s: u64) -> Self {
@@ -46,6 +115,217 @@ impl VDuration {
     /// Get the duration as seconds (floating-point). Handy for rendering/logging.
     #[inline(always)]
     pub fn as_secs_f64(self) -> f64 { (self.0 as f64) / 1_000_000_000.0 }
+
+    /// Create from floating-point seconds, rounded to the nearest nanosecond.
+    /// Saturating: negative values and NaN give `ZERO`, values past `MAX` give `MAX`.
+    #[inline(always)]
+    pub fn from_secs_f64(secs: f64) -> Self { saturating_from_nanos_f64(secs * NANOS_PER_SEC as f64) }
+
+    /// Whole seconds (truncating).
+    #[inline(always)]
+    pub const fn as_secs(self) -> u64 { self.0 / NANOS_PER_SEC }
+
+    /// Whole milliseconds (truncating).
+    #[inline(always)]
+    pub const fn as_millis(self) -> u64 { self.0 / NANOS_PER_MILLI }
+
+    /// Whole microseconds (truncating).
+    #[inline(always)]
+    pub const fn as_micros(self) -> u64 { self.0 / NANOS_PER_MICRO }
+
+    /// Fractional part in nanoseconds (`0..1_000_000_000`).
+    #[inline(always)]
+    pub const fn subsec_nanos(self) -> u32 { (self.0 % NANOS_PER_SEC) as u32 }
+
+    /// Fractional part in whole microseconds (`0..1_000_000`).
+    #[inline(always)]
+    pub const fn subsec_micros(self) -> u32 { self.subsec_nanos() / 1_000 }
+
+    /// Fractional part in whole milliseconds (`0..1_000`).
+    #[inline(always)]
+    pub const fn subsec_millis(self) -> u32 { self.subsec_nanos() / 1_000_000 }
+
+    /// Whether this is `ZERO`.
+    #[inline(always)]
+    pub const fn is_zero(self) -> bool { self.0 == 0 }
+
+    /// `self + rhs`, or `None` on overflow.
+    #[inline(always)]
+    pub const fn checked_add(self, rhs: VDuration) -> Option<Self> {
+        match self.0.checked_add(rhs.0) { Some(ns) => Some(Self(ns)), None => None }
+    }
+
+    /// `self - rhs`, or `None` if `rhs` is longer.
+    #[inline(always)]
+    pub const fn checked_sub(self, rhs: VDuration) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) { Some(ns) => Some(Self(ns)), None => None }
+    }
+
+    /// `self * rhs`, or `None` on overflow.
+    #[inline(always)]
+    pub const fn checked_mul(self, rhs: u64) -> Option<Self> {
+        match self.0.checked_mul(rhs) { Some(ns) => Some(Self(ns)), None => None }
+    }
+
+    /// `self / rhs` (truncating), or `None` if `rhs` is zero.
+    #[inline(always)]
+    pub const fn checked_div(self, rhs: u64) -> Option<Self> {
+        match self.0.checked_div(rhs) { Some(ns) => Some(Self(ns)), None => None }
+    }
+
+    /// `self + rhs`, clamped to `MAX`.
+    #[inline(always)]
+    pub const fn saturating_add(self, rhs: VDuration) -> Self { Self(self.0.saturating_add(rhs.0)) }
+
+    /// `self - rhs`, clamped to `ZERO`.
+    #[inline(always)]
+    pub const fn saturating_sub(self, rhs: VDuration) -> Self { Self(self.0.saturating_sub(rhs.0)) }
+
+    /// `self * rhs`, clamped to `MAX`.
+    #[inline(always)]
+    pub const fn saturating_mul(self, rhs: u64) -> Self { Self(self.0.saturating_mul(rhs)) }
+
+    /// `self + rhs` modulo 2^64 ns.
+    #[inline(always)]
+    pub const fn wrapping_add(self, rhs: VDuration) -> Self { Self(self.0.wrapping_add(rhs.0)) }
+
+    /// `self - rhs` modulo 2^64 ns.
+    #[inline(always)]
+    pub const fn wrapping_sub(self, rhs: VDuration) -> Self { Self(self.0.wrapping_sub(rhs.0)) }
+
+    /// `self * rhs` modulo 2^64 ns.
+    #[inline(always)]
+    pub const fn wrapping_mul(self, rhs: u64) -> Self { Self(self.0.wrapping_mul(rhs)) }
+
+    /// Scale by a float factor, rounded to the nearest nanosecond and saturating like
+    /// `from_secs_f64`.
+    #[inline(always)]
+    pub fn mul_f64(self, rhs: f64) -> Self { saturating_from_nanos_f64(self.0 as f64 * rhs) }
+}
+
+/// Signed virtual duration in **nanoseconds**, e.g. the gap between two instants in
+/// either order. Arithmetic is **saturating**; `abs` is exact (it returns a `VDuration`),
+/// while negating `MIN` saturates to `MAX`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct VSignedDuration(pub i64);
+
+impl VSignedDuration {
+    pub const ZERO: VSignedDuration = VSignedDuration(0);
+    pub const MIN: VSignedDuration = VSignedDuration(i64::MIN);
+    pub const MAX: VSignedDuration = VSignedDuration(i64::MAX);
+
+    /// Create from signed nanoseconds (identity).
+    #[inline(always)]
+    pub const fn from_nanos(ns: i64) -> Self { Self(ns) }
+
+    /// Get the duration in signed nanoseconds (identity).
+    #[inline(always)]
+    pub const fn as_nanos(self) -> i64 { self.0 }
+
+    /// Signed seconds (floating-point).
+    #[inline(always)]
+    pub fn as_secs_f64(self) -> f64 { (self.0 as f64) / 1_000_000_000.0 }
+
+    #[inline(always)]
+    pub const fn is_negative(self) -> bool { self.0 < 0 }
+
+    #[inline(always)]
+    pub const fn is_positive(self) -> bool { self.0 > 0 }
+
+    /// `-1`, `0` or `1`.
+    #[inline(always)]
+    pub const fn signum(self) -> i64 { self.0.signum() }
+
+    /// Magnitude; exact for every value, `MIN` included.
+    #[inline(always)]
+    pub const fn abs(self) -> VDuration { VDuration(self.0.unsigned_abs()) }
+
+    /// `-self`, or `None` for `MIN`.
+    #[inline(always)]
+    pub const fn checked_neg(self) -> Option<Self> {
+        match self.0.checked_neg() { Some(ns) => Some(Self(ns)), None => None }
+    }
+
+    /// `self + rhs`, or `None` on overflow.
+    #[inline(always)]
+    pub const fn checked_add(self, rhs: VSignedDuration) -> Option<Self> {
+        match self.0.checked_add(rhs.0) { Some(ns) => Some(Self(ns)), None => None }
+    }
+
+    /// `self - rhs`, or `None` on overflow.
+    #[inline(always)]
+    pub const fn checked_sub(self, rhs: VSignedDuration) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) { Some(ns) => Some(Self(ns)), None => None }
+    }
+
+    /// The duration if it is not negative.
+    #[inline(always)]
+    pub const fn to_unsigned(self) -> Option<VDuration> {
+        if self.0 < 0 { None } else { Some(VDuration(self.0 as u64)) }
+    }
+}
+
+impl core::ops::Neg for VSignedDuration {
+    type Output = VSignedDuration;
+    #[inline(always)]
+    fn neg(self) -> Self::Output {
+        VSignedDuration(self.0.saturating_neg())
+    }
+}
+
+impl core::ops::Add for VSignedDuration {
+    type Output = VSignedDuration;
+    #[inline(always)]
+    fn add(self, rhs: VSignedDuration) -> Self::Output {
+        VSignedDuration(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl core::ops::Sub for VSignedDuration {
+    type Output = VSignedDuration;
+    #[inline(always)]
+    fn sub(self, rhs: VSignedDuration) -> Self::Output {
+        VSignedDuration(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl core::ops::AddAssign for VSignedDuration {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: VSignedDuration) {
+        self.0 = self.0.saturating_add(rhs.0);
+    }
+}
+
+impl core::ops::SubAssign for VSignedDuration {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: VSignedDuration) {
+        self.0 = self.0.saturating_sub(rhs.0);
+    }
+}
+
+/// Saturating, like `Add`.
+impl core::iter::Sum for VSignedDuration {
+    fn sum<I: Iterator<Item = VSignedDuration>>(iter: I) -> Self {
+        iter.fold(VSignedDuration::ZERO, |acc, d| acc + d)
+    }
+}
+
+/// Fails for durations over `i64::MAX` nanoseconds (~292 years).
+impl TryFrom<VDuration> for VSignedDuration {
+    type Error = core::num::TryFromIntError;
+    #[inline(always)]
+    fn try_from(d: VDuration) -> Result<Self, Self::Error> {
+        i64::try_from(d.0).map(VSignedDuration)
+    }
+}
+
+/// Fails for negative durations.
+impl TryFrom<VSignedDuration> for VDuration {
+    type Error = core::num::TryFromIntError;
+    #[inline(always)]
+    fn try_from(d: VSignedDuration) -> Result<Self, Self::Error> {
+        u64::try_from(d.0).map(VDuration)
+    }
 }
 
 /* ---- Arithmetic between VInstant and VDuration ---- */
@@ -65,6 +345,23 @@ impl core::ops::AddAssign<VDuration> for VInstant {
     }
 }
 
+impl core::ops::Sub<VDuration> for VInstant {
+    type Output = VInstant;
+    #[inline(always)]
+    fn sub(self, rhs: VDuration) -> Self::Output {
+        VInstant(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+impl core::ops::SubAssign<VDuration> for VInstant {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: VDuration) {
+        self.0 = self.0.wrapping_sub(rhs.0);
+    }
+}
+
+/// Wraps when `rhs` is later; use `signed_duration_since` or `checked_duration_since`
+/// when the order is not known.
 impl core::ops::Sub<VInstant> for VInstant {
     type Output = VDuration;
     #[inline(always)]
@@ -99,6 +396,83 @@ impl core::ops::AddAssign for VDuration {
     }
 }
 
+impl core::ops::SubAssign for VDuration {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: VDuration) {
+        self.0 = self.0.saturating_sub(rhs.0);
+    }
+}
+
+impl core::ops::Mul<u64> for VDuration {
+    type Output = VDuration;
+    #[inline(always)]
+    fn mul(self, rhs: u64) -> Self::Output {
+        VDuration(self.0.saturating_mul(rhs))
+    }
+}
+
+impl core::ops::Mul<VDuration> for u64 {
+    type Output = VDuration;
+    #[inline(always)]
+    fn mul(self, rhs: VDuration) -> Self::Output {
+        VDuration(rhs.0.saturating_mul(self))
+    }
+}
+
+impl core::ops::MulAssign<u64> for VDuration {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: u64) {
+        self.0 = self.0.saturating_mul(rhs);
+    }
+}
+
+/// Truncating; panics if `rhs` is zero, like integer division.
+impl core::ops::Div<u64> for VDuration {
+    type Output = VDuration;
+    #[inline(always)]
+    fn div(self, rhs: u64) -> Self::Output {
+        VDuration(self.0 / rhs)
+    }
+}
+
+impl core::ops::DivAssign<u64> for VDuration {
+    #[inline(always)]
+    fn div_assign(&mut self, rhs: u64) {
+        self.0 /= rhs;
+    }
+}
+
+/// How many whole `rhs` fit in `self`; panics if `rhs` is zero.
+impl core::ops::Div for VDuration {
+    type Output = u64;
+    #[inline(always)]
+    fn div(self, rhs: VDuration) -> Self::Output {
+        self.0 / rhs.0
+    }
+}
+
+/// What is left after taking out whole multiples of `rhs`; panics if `rhs` is zero.
+impl core::ops::Rem for VDuration {
+    type Output = VDuration;
+    #[inline(always)]
+    fn rem(self, rhs: VDuration) -> Self::Output {
+        VDuration(self.0 % rhs.0)
+    }
+}
+
+/// Saturating, like `Add`.
+impl core::iter::Sum for VDuration {
+    fn sum<I: Iterator<Item = VDuration>>(iter: I) -> Self {
+        iter.fold(VDuration::ZERO, |acc, d| acc + d)
+    }
+}
+
+impl<'a> core::iter::Sum<&'a VDuration> for VDuration {
+    fn sum<I: Iterator<Item = &'a VDuration>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
 /* ---- Interop with core::time::Duration ---- */
 
 impl From<VDuration> for VInstant {
@@ -107,6 +481,14 @@ impl From<VDuration> for VInstant {
     }
 }
 
+/// Lossless: every `VDuration` fits in a `Duration`, so `TryFrom` never fails.
+impl From<VDuration> for core::time::Duration {
+    #[inline(always)]
+    fn from(d: VDuration) -> Self {
+        core::time::Duration::from_nanos(d.0)
+    }
+}
+
 impl From<core::time::Duration> for VDuration {
     #[inline(always)]
     fn from(d: core::time::Duration) -> Self {
@@ -117,3 +499,247 @@ impl From<core::time::Duration> for VDuration {
         VDuration(ns)
     }
 }
+
+/* ---- Formatting and parsing ---- */
+
+/// Scratch buffer for the `Display` impls; the longest output is `VSignedDuration::MIN`
+/// (27 bytes).
+type FmtBuf = heapless::String<32>;
+
+/// Write `s` honouring the formatter's width, fill and alignment (left by default).
+/// Unlike `Formatter::pad`, precision is left to the caller rather than truncating `s`.
+pub(crate) fn pad(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    let Some(fill) = f.width().and_then(|w| w.checked_sub(s.chars().count())) else {
+        return f.write_str(s);
+    };
+    let (pre, post) = match f.align() {
+        Some(fmt::Alignment::Right) => (fill, 0),
+        Some(fmt::Alignment::Center) => (fill / 2, fill - fill / 2),
+        _ => (0, fill),
+    };
+    let c = f.fill();
+    for _ in 0..pre {
+        f.write_char(c)?;
+    }
+    f.write_str(s)?;
+    for _ in 0..post {
+        f.write_char(c)?;
+    }
+    Ok(())
+}
+
+/// `int`, then `frac / scale` as decimals without trailing zeros; `scale` is a power of ten.
+fn write_decimal(out: &mut impl Write, int: u64, frac: u64, scale: u64) -> fmt::Result {
+    write!(out, "{int}")?;
+    if frac == 0 {
+        return Ok(());
+    }
+    let (mut frac, mut width) = (frac, scale.ilog10() as usize);
+    while frac.is_multiple_of(10) {
+        frac /= 10;
+        width -= 1;
+    }
+    write!(out, ".{frac:0width$}")
+}
+
+fn write_duration(out: &mut impl Write, ns: u64) -> fmt::Result {
+    if ns == 0 {
+        return out.write_str("0s");
+    }
+    if ns < NANOS_PER_SEC {
+        let (unit, scale) = if ns >= NANOS_PER_MILLI {
+            ("ms", NANOS_PER_MILLI)
+        } else if ns >= NANOS_PER_MICRO {
+            ("us", NANOS_PER_MICRO)
+        } else {
+            ("ns", 1)
+        };
+        write_decimal(out, ns / scale, ns % scale, scale)?;
+        return out.write_str(unit);
+    }
+    let secs = ns / NANOS_PER_SEC;
+    for (n, unit) in [(secs / 86_400, "d"), (secs / 3_600 % 24, "h"), (secs / 60 % 60, "m")] {
+        if n > 0 {
+            write!(out, "{n}{unit}")?;
+        }
+    }
+    let (s, frac) = (secs % 60, ns % NANOS_PER_SEC);
+    if s > 0 || frac > 0 {
+        write_decimal(out, s, frac, NANOS_PER_SEC)?;
+        out.write_char('s')?;
+    }
+    Ok(())
+}
+
+/// Short form with the largest sensible units: `250ms`, `1.5s`, `3h2m`, `1d0.5s`.
+/// Sub-second values use a single unit (`ns`, `us`, `ms`); longer ones are split into
+/// days, hours, minutes and (fractional) seconds, omitting zero parts. Exact: the output
+/// parses back to the same value. Width and alignment are honoured.
+impl fmt::Display for VDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = FmtBuf::new();
+        write_duration(&mut buf, self.0)?;
+        pad(f, &buf)
+    }
+}
+
+/// Like `VDuration`, with a leading `-` when negative: `-250ms`.
+impl fmt::Display for VSignedDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = FmtBuf::new();
+        if self.is_negative() {
+            buf.push('-').map_err(|_| fmt::Error)?;
+        }
+        write_duration(&mut buf, self.abs().0)?;
+        pad(f, &buf)
+    }
+}
+
+/// Raw form: seconds since the clock's epoch with nanosecond digits, e.g. `12.000250000s`.
+/// The precision sets the number of fractional digits (truncating, at most 9), so
+/// `{:.3}` prints `12.000s`. For a civil date and time use `Calendar::display`.
+impl fmt::Display for VInstant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = f.precision().unwrap_or(9).min(9);
+        let mut buf = FmtBuf::new();
+        write!(buf, "{}", self.0 / NANOS_PER_SEC)?;
+        if digits > 0 {
+            let frac = self.0 % NANOS_PER_SEC / 10u64.pow(9 - digits as u32);
+            write!(buf, ".{frac:0digits$}")?;
+        }
+        buf.push('s').map_err(|_| fmt::Error)?;
+        pad(f, &buf)
+    }
+}
+
+/// Why a duration string was rejected; positions are byte offsets into the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseDurationError {
+    /// The input was empty.
+    Empty,
+    /// Expected a number at this position.
+    InvalidNumber(usize),
+    /// The number ending at this position has no unit.
+    MissingUnit(usize),
+    /// The unit at this position is not one of `ns`, `us` (or `µs`), `ms`, `s`, `m`, `h`, `d`.
+    UnknownUnit(usize),
+    /// The value does not fit.
+    Overflow,
+}
+
+impl ParseDurationError {
+    fn shifted(self, by: usize) -> Self {
+        match self {
+            Self::InvalidNumber(at) => Self::InvalidNumber(at + by),
+            Self::MissingUnit(at) => Self::MissingUnit(at + by),
+            Self::UnknownUnit(at) => Self::UnknownUnit(at + by),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for ParseDurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty duration"),
+            Self::InvalidNumber(at) => write!(f, "expected a number at byte {at}"),
+            Self::MissingUnit(at) => write!(f, "missing unit at byte {at}"),
+            Self::UnknownUnit(at) => write!(f, "unknown unit at byte {at}"),
+            Self::Overflow => f.write_str("duration out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDurationError {}
+
+fn unit_scale(unit: &str) -> Option<u64> {
+    Some(match unit {
+        "ns" => 1,
+        "us" | "µs" => NANOS_PER_MICRO,
+        "ms" => NANOS_PER_MILLI,
+        "s" => NANOS_PER_SEC,
+        "m" => 60 * NANOS_PER_SEC,
+        "h" => 3_600 * NANOS_PER_SEC,
+        "d" => 86_400 * NANOS_PER_SEC,
+        _ => return None,
+    })
+}
+
+/// Parses what `Display` writes, and more: one or more `<number><unit>` parts such as
+/// `250ms`, `1.5s`, `3h2m` or `1h0.5m`, summed; numbers may have a fraction (truncated
+/// to whole nanoseconds). A bare `0` is accepted. No whitespace or sign is allowed.
+impl FromStr for VDuration {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let b = s.as_bytes();
+        if b.is_empty() {
+            return Err(ParseDurationError::Empty);
+        }
+        if s == "0" {
+            return Ok(VDuration::ZERO);
+        }
+        let mut total = 0u64;
+        let mut i = 0;
+        while i < b.len() {
+            let start = i;
+            let mut int = 0u64;
+            while let Some(d) = b.get(i).filter(|c| c.is_ascii_digit()) {
+                int = int
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(u64::from(d - b'0')))
+                    .ok_or(ParseDurationError::Overflow)?;
+                i += 1;
+            }
+            let mut digits = i - start;
+            // The fraction as `num / den`; digits past 10^-19 cannot reach a nanosecond.
+            let (mut num, mut den) = (0u128, 1u128);
+            if b.get(i) == Some(&b'.') {
+                i += 1;
+                while let Some(d) = b.get(i).filter(|c| c.is_ascii_digit()) {
+                    if den < 10u128.pow(19) {
+                        num = num * 10 + u128::from(d - b'0');
+                        den *= 10;
+                    }
+                    digits += 1;
+                    i += 1;
+                }
+            }
+            if digits == 0 {
+                return Err(ParseDurationError::InvalidNumber(start));
+            }
+            let unit_start = i;
+            while i < b.len() && !b[i].is_ascii_digit() && b[i] != b'.' {
+                i += 1;
+            }
+            if unit_start == i {
+                return Err(ParseDurationError::MissingUnit(i));
+            }
+            let scale = unit_scale(&s[unit_start..i]).ok_or(ParseDurationError::UnknownUnit(unit_start))?;
+            let part = int
+                .checked_mul(scale)
+                .and_then(|n| n.checked_add((num * u128::from(scale) / den) as u64))
+                .ok_or(ParseDurationError::Overflow)?;
+            total = total.checked_add(part).ok_or(ParseDurationError::Overflow)?;
+        }
+        Ok(VDuration(total))
+    }
+}
+
+/// A `VDuration` with an optional leading `-` or `+`.
+impl FromStr for VSignedDuration {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (neg, rest) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let shift = s.len() - rest.len();
+        let ns = rest.parse::<VDuration>().map_err(|e| e.shifted(shift))?.0;
+        let ns = if neg { 0i64.checked_sub_unsigned(ns) } else { i64::try_from(ns).ok() };
+        ns.map(VSignedDuration).ok_or(ParseDurationError::Overflow)
+    }
+}
@@ -0,0 +1,525 @@
+// kairos-core/src/tz.rs
+//! IANA time zones from compiled TZif data (RFC 8536).
+//!
+//! A `TimeZone` is a validated view over the bytes of a zone file, so it needs no
+//! allocation: embed the data with `include_bytes!` on `no_std` targets, or read it from
+//! the system zoneinfo database with `ZoneFile` under `std`. Instants up to the file's
+//! last transition use its table; later ones follow the POSIX TZ rule in the footer, so
+//! DST keeps working past the end of the table (2037 for most zone files).
+
+use core::fmt;
+
+use crate::calendar::{civil_from_days, days_from_civil, days_in_month};
+
+const MAGIC: &[u8; 4] = b"TZif";
+const HEADER_LEN: usize = 44;
+
+/// Why TZif data was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TzError {
+    /// Not TZif data: the magic bytes are missing.
+    NotTzif,
+    /// The data ends before the sizes in its header say it should.
+    Truncated,
+    /// A count, index, offset or transition time is out of range.
+    InvalidData,
+    /// The POSIX TZ string in the footer could not be parsed.
+    InvalidFooter,
+}
+
+impl fmt::Display for TzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TzError::NotTzif => "not TZif data",
+            TzError::Truncated => "truncated TZif data",
+            TzError::InvalidData => "invalid TZif data",
+            TzError::InvalidFooter => "invalid TZ string in TZif footer",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TzError {}
+
+/// The offset from UTC in effect at some instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TzOffset<'a> {
+    /// Seconds east of UTC (negative west of Greenwich).
+    pub utc_offset: i32,
+    /// Whether daylight saving time is in effect.
+    pub is_dst: bool,
+    /// Designation such as `CET`, `EDT` or `+03`.
+    pub abbreviation: &'a str,
+}
+
+/// Where each part of the data block lives.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Layout {
+    /// Transition times are 64-bit (version 2 and later) rather than 32-bit.
+    wide: bool,
+    times: usize,
+    transitions: usize,
+    indices: usize,
+    types: usize,
+    type_count: usize,
+    chars: usize,
+    char_count: usize,
+    footer: Option<Rule>,
+}
+
+/// A time zone: UTC offsets, DST flags and abbreviations over time.
+///
+/// Borrows the TZif bytes it was parsed from and is cheap to copy.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct TimeZone<'a> {
+    data: &'a [u8],
+    layout: Layout,
+}
+
+impl<'a> TimeZone<'a> {
+    /// Parse and validate TZif data (versions 1 to 4), e.g. from
+    /// `include_bytes!("/usr/share/zoneinfo/Europe/Berlin")`.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, TzError> {
+        Ok(Self { data, layout: parse(data)? })
+    }
+
+    /// The offset in effect at `unix_secs` (seconds since 1970-01-01 UTC).
+    pub fn offset_at(&self, unix_secs: i64) -> TzOffset<'a> {
+        let l = &self.layout;
+        let n = l.transitions;
+        if n == 0 || unix_secs >= self.transition(n - 1) {
+            if let Some(rule) = &l.footer {
+                return rule.offset_at(self.data, unix_secs);
+            }
+        }
+        // Times before the first transition use type 0 (RFC 8536, section 3.2).
+        if n == 0 || unix_secs < self.transition(0) {
+            return self.local_type(0);
+        }
+        // Last transition at or before `unix_secs`.
+        let (mut lo, mut hi) = (0, n);
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.transition(mid) <= unix_secs {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        self.local_type(usize::from(self.data[l.indices + lo]))
+    }
+
+    fn transition(&self, i: usize) -> i64 {
+        if self.layout.wide {
+            be_i64(self.data, self.layout.times + i * 8)
+        } else {
+            i64::from(be_i32(self.data, self.layout.times + i * 4))
+        }
+    }
+
+    fn local_type(&self, i: usize) -> TzOffset<'a> {
+        let at = self.layout.types + i * 6;
+        let chars = &self.data[self.layout.chars..self.layout.chars + self.layout.char_count];
+        TzOffset {
+            utc_offset: be_i32(self.data, at),
+            is_dst: self.data[at + 4] != 0,
+            abbreviation: designation(chars, usize::from(self.data[at + 5])).unwrap_or(""),
+        }
+    }
+}
+
+impl fmt::Debug for TimeZone<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let footer = self.layout.footer.map(|r| &self.data[r.text.0..r.text.1]);
+        f.debug_struct("TimeZone")
+            .field("transitions", &self.layout.transitions)
+            .field("types", &self.layout.type_count)
+            .field("footer", &footer.and_then(|b| core::str::from_utf8(b).ok()))
+            .finish()
+    }
+}
+
+fn be_i32(data: &[u8], at: usize) -> i32 {
+    i32::from_be_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+}
+
+fn be_i64(data: &[u8], at: usize) -> i64 {
+    let mut b = [0; 8];
+    b.copy_from_slice(&data[at..at + 8]);
+    i64::from_be_bytes(b)
+}
+
+/// The NUL-terminated abbreviation starting at `at`.
+fn designation(chars: &[u8], at: usize) -> Option<&str> {
+    let tail = chars.get(at..)?;
+    let len = tail.iter().position(|&c| c == 0)?;
+    core::str::from_utf8(&tail[..len]).ok()
+}
+
+/// `timecnt`, `typecnt`, … from the header at `at`, in file order.
+struct Counts {
+    isut: usize,
+    isstd: usize,
+    leap: usize,
+    time: usize,
+    types: usize,
+    chars: usize,
+}
+
+impl Counts {
+    /// Length of the data block that follows the header.
+    fn block_len(&self, time_size: usize) -> Option<usize> {
+        let parts = [
+            self.time.checked_mul(time_size + 1)?,
+            self.types.checked_mul(6)?,
+            self.chars,
+            self.leap.checked_mul(time_size + 4)?,
+            self.isstd,
+            self.isut,
+        ];
+        parts.iter().try_fold(0usize, |sum, &n| sum.checked_add(n))
+    }
+}
+
+fn header(data: &[u8], at: usize) -> Result<(u8, Counts), TzError> {
+    if at == 0 && !data.starts_with(MAGIC) {
+        return Err(TzError::NotTzif);
+    }
+    let h = data.get(at..).and_then(|h| h.get(..HEADER_LEN)).ok_or(TzError::Truncated)?;
+    if !h.starts_with(MAGIC) {
+        return Err(TzError::InvalidData);
+    }
+    let count = |i: usize| {
+        let at = 20 + 4 * i;
+        usize::try_from(u32::from_be_bytes([h[at], h[at + 1], h[at + 2], h[at + 3]])).map_err(|_| TzError::InvalidData)
+    };
+    let counts = Counts {
+        isut: count(0)?,
+        isstd: count(1)?,
+        leap: count(2)?,
+        time: count(3)?,
+        types: count(4)?,
+        chars: count(5)?,
+    };
+    Ok((h[4], counts))
+}
+
+fn parse(data: &[u8]) -> Result<Layout, TzError> {
+    let (version, v1) = header(data, 0)?;
+    let v1_len = v1.block_len(4).ok_or(TzError::InvalidData)?;
+    // Version 2+ repeats everything with 64-bit times after the version 1 block.
+    let (wide, start, c) = if version >= b'2' {
+        let at = HEADER_LEN.checked_add(v1_len).ok_or(TzError::InvalidData)?;
+        let (_, c) = header(data, at)?;
+        (true, at + HEADER_LEN, c)
+    } else if version == 0 {
+        (false, HEADER_LEN, v1)
+    } else {
+        return Err(TzError::InvalidData);
+    };
+    let time_size = if wide { 8 } else { 4 };
+    let end = c.block_len(time_size).and_then(|n| n.checked_add(start)).ok_or(TzError::InvalidData)?;
+    if data.len() < end {
+        return Err(TzError::Truncated);
+    }
+    if c.types == 0 || c.types > 256 || c.chars == 0 {
+        return Err(TzError::InvalidData);
+    }
+    let indices = start + c.time * time_size;
+    let types = indices + c.time;
+    let chars = types + c.types * 6;
+    let mut layout = Layout {
+        wide,
+        times: start,
+        transitions: c.time,
+        indices,
+        types,
+        type_count: c.types,
+        chars,
+        char_count: c.chars,
+        footer: None,
+    };
+    let tz = TimeZone { data, layout };
+    for i in 0..c.time {
+        if usize::from(data[indices + i]) >= c.types || (i > 0 && tz.transition(i - 1) >= tz.transition(i)) {
+            return Err(TzError::InvalidData);
+        }
+    }
+    let char_data = &data[chars..chars + c.chars];
+    for i in 0..c.types {
+        let at = types + i * 6;
+        let offset = be_i32(data, at);
+        if offset == i32::MIN || data[at + 4] > 1 || designation(char_data, usize::from(data[at + 5])).is_none() {
+            return Err(TzError::InvalidData);
+        }
+    }
+    if wide {
+        layout.footer = footer(data, end)?;
+    }
+    Ok(layout)
+}
+
+/// The footer: the POSIX TZ string between two newlines, if not empty.
+fn footer(data: &[u8], at: usize) -> Result<Option<Rule>, TzError> {
+    let rest = &data[at..];
+    if rest.is_empty() {
+        return Ok(None);
+    }
+    if rest[0] != b'\n' {
+        return Err(TzError::InvalidFooter);
+    }
+    let len = rest[1..].iter().position(|&c| c == b'\n').ok_or(TzError::InvalidFooter)?;
+    if len == 0 {
+        return Ok(None);
+    }
+    let mut p = Posix { data, pos: at + 1, end: at + 1 + len };
+    p.rule().filter(|_| p.pos == p.end).map(Some).ok_or(TzError::InvalidFooter)
+}
+
+/* ---- POSIX TZ strings, e.g. `CET-1CEST,M3.5.0,M10.5.0/3` ---- */
+
+/// Day of the year on which DST starts or ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RuleDay {
+    /// `Jn`: day 1 to 365, never counting February 29.
+    Julian(u16),
+    /// `n`: day 0 to 365, counting February 29.
+    Ordinal(u16),
+    /// `Mm.w.d`: weekday `d` (0 = Sunday) of week `w` of month `m`; week 5 is the last.
+    MonthWeek { month: u8, week: u8, weekday: u8 },
+}
+
+impl RuleDay {
+    /// Days since 1970-01-01 of this day in `year`.
+    fn day(self, year: i32) -> i64 {
+        let jan1 = days_from_civil(year, 1, 1);
+        match self {
+            RuleDay::Julian(n) => {
+                let leap_day = n >= 60 && days_in_month(i64::from(year), 2) == 29;
+                jan1 + i64::from(n) - 1 + i64::from(leap_day)
+            }
+            RuleDay::Ordinal(n) => jan1 + i64::from(n),
+            RuleDay::MonthWeek { month, week, weekday } => {
+                let month = u32::from(month);
+                let first = days_from_civil(year, month, 1);
+                // 1970-01-01 was a Thursday.
+                let first_weekday = (first + 4).rem_euclid(7);
+                let mut day = first + (i64::from(weekday) - first_weekday).rem_euclid(7) + 7 * (i64::from(week) - 1);
+                let len = i64::from(days_in_month(i64::from(year), month));
+                while day >= first + len {
+                    day -= 7;
+                }
+                day
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Dst {
+    name: (usize, usize),
+    offset: i32,
+    start: RuleDay,
+    /// Local (standard) time of day of the start, in seconds; may be negative or past 24h.
+    start_time: i32,
+    end: RuleDay,
+    /// Local (daylight) time of day of the end.
+    end_time: i32,
+}
+
+/// A parsed TZ string; names are byte ranges into the zone data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Rule {
+    text: (usize, usize),
+    std_name: (usize, usize),
+    std_offset: i32,
+    dst: Option<Dst>,
+}
+
+impl Rule {
+    fn offset_at<'a>(&self, data: &'a [u8], unix_secs: i64) -> TzOffset<'a> {
+        let name = |(a, b): (usize, usize)| core::str::from_utf8(&data[a..b]).unwrap_or("");
+        let std = TzOffset { utc_offset: self.std_offset, is_dst: false, abbreviation: name(self.std_name) };
+        let Some(dst) = self.dst else {
+            return std;
+        };
+        let local_day = unix_secs.saturating_add(i64::from(self.std_offset)).div_euclid(86_400);
+        let (year, _, _) = civil_from_days(local_day);
+        let start = dst.start.day(year) * 86_400 + i64::from(dst.start_time) - i64::from(self.std_offset);
+        let end = dst.end.day(year) * 86_400 + i64::from(dst.end_time) - i64::from(dst.offset);
+        // Southern-hemisphere rules end DST before they start it within a year.
+        let in_dst = if start <= end {
+            (start..end).contains(&unix_secs)
+        } else {
+            !(end..start).contains(&unix_secs)
+        };
+        if in_dst {
+            TzOffset { utc_offset: dst.offset, is_dst: true, abbreviation: name(dst.name) }
+        } else {
+            std
+        }
+    }
+}
+
+struct Posix<'a> {
+    data: &'a [u8],
+    pos: usize,
+    end: usize,
+}
+
+impl Posix<'_> {
+    fn peek(&self) -> Option<u8> {
+        (self.pos < self.end).then(|| self.data[self.pos])
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        let hit = self.peek() == Some(c);
+        self.pos += usize::from(hit);
+        hit
+    }
+
+    fn rule(&mut self) -> Option<Rule> {
+        let start = self.pos;
+        let std_name = self.name()?;
+        // POSIX offsets count west of Greenwich.
+        let std_offset = -self.time(24)?;
+        let mut rule = Rule { text: (start, self.end), std_name, std_offset, dst: None };
+        if self.peek().is_none() {
+            return Some(rule);
+        }
+        let name = self.name()?;
+        let offset = match self.peek() {
+            Some(c) if c == b'+' || c == b'-' || c.is_ascii_digit() => -self.time(24)?,
+            _ => std_offset + 3_600,
+        };
+        // Without a rule, use the current US one like other implementations do.
+        let (start, start_time, end, end_time) = if self.eat(b',') {
+            let (start, start_time) = self.date()?;
+            if !self.eat(b',') {
+                return None;
+            }
+            let (end, end_time) = self.date()?;
+            (start, start_time, end, end_time)
+        } else {
+            let start = RuleDay::MonthWeek { month: 3, week: 2, weekday: 0 };
+            let end = RuleDay::MonthWeek { month: 11, week: 1, weekday: 0 };
+            (start, 7_200, end, 7_200)
+        };
+        rule.dst = Some(Dst { name, offset, start, start_time, end, end_time });
+        Some(rule)
+    }
+
+    /// `EST` (three or more letters) or `<+03>`; returns the name's byte range.
+    fn name(&mut self) -> Option<(usize, usize)> {
+        if self.eat(b'<') {
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == b'+' || c == b'-') {
+                self.pos += 1;
+            }
+            let end = self.pos;
+            (end > start && self.eat(b'>')).then_some((start, end))
+        } else {
+            let start = self.pos;
+            while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+                self.pos += 1;
+            }
+            (self.pos - start >= 3).then_some((start, self.pos))
+        }
+    }
+
+    fn number(&mut self, max: u32) -> Option<u32> {
+        let start = self.pos;
+        let mut n = 0u32;
+        while let Some(c) = self.peek().filter(u8::is_ascii_digit) {
+            n = n.checked_mul(10)?.checked_add(u32::from(c - b'0'))?;
+            self.pos += 1;
+        }
+        (self.pos > start && n <= max).then_some(n)
+    }
+
+    /// `[+-]hh[:mm[:ss]]` in seconds.
+    fn time(&mut self, max_hours: u32) -> Option<i32> {
+        let neg = self.eat(b'-');
+        if !neg {
+            self.eat(b'+');
+        }
+        let mut secs = self.number(max_hours)? * 3_600;
+        if self.eat(b':') {
+            secs += self.number(59)? * 60;
+            if self.eat(b':') {
+                secs += self.number(59)?;
+            }
+        }
+        let secs = i32::try_from(secs).ok()?;
+        Some(if neg { -secs } else { secs })
+    }
+
+    /// `Jn`, `n` or `Mm.w.d`, then an optional `/time` (default 02:00).
+    fn date(&mut self) -> Option<(RuleDay, i32)> {
+        let day = if self.eat(b'J') {
+            RuleDay::Julian(self.number(365).filter(|&n| n >= 1)? as u16)
+        } else if self.eat(b'M') {
+            let month = self.number(12).filter(|&m| m >= 1)? as u8;
+            if !self.eat(b'.') {
+                return None;
+            }
+            let week = self.number(5).filter(|&w| w >= 1)? as u8;
+            if !self.eat(b'.') {
+                return None;
+            }
+            let weekday = self.number(6)? as u8;
+            RuleDay::MonthWeek { month, week, weekday }
+        } else {
+            RuleDay::Ordinal(self.number(365)? as u16)
+        };
+        // RFC 8536 extends the hour range to ±167.
+        let time = if self.eat(b'/') { self.time(167)? } else { 7_200 };
+        Some((day, time))
+    }
+}
+
+/// A zone file read from disk, owning its bytes; `time_zone` borrows a `TimeZone` from it.
+#[cfg(feature = "std")]
+#[derive(Clone, PartialEq, Eq)]
+pub struct ZoneFile {
+    data: std::vec::Vec<u8>,
+    layout: Layout,
+}
+
+#[cfg(feature = "std")]
+impl ZoneFile {
+    /// Load the zone `name`, e.g. `"Europe/Berlin"`, from the directory in `$TZDIR`
+    /// or else `/usr/share/zoneinfo`.
+    pub fn load(name: &str) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+
+        if name.is_empty() || name.starts_with('/') || name.split('/').any(|c| c.is_empty() || c == "..") {
+            return Err(Error::new(ErrorKind::InvalidInput, "invalid time zone name"));
+        }
+        let dir = std::env::var_os("TZDIR").unwrap_or_else(|| "/usr/share/zoneinfo".into());
+        Self::load_path(std::path::Path::new(&dir).join(name))
+    }
+
+    /// Load a TZif file, e.g. `/etc/localtime`.
+    pub fn load_path<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let data = std::fs::read(path)?;
+        Self::from_vec(data).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Take ownership of TZif data.
+    pub fn from_vec(data: std::vec::Vec<u8>) -> Result<Self, TzError> {
+        let layout = parse(&data)?;
+        Ok(Self { data, layout })
+    }
+
+    pub fn time_zone(&self) -> TimeZone<'_> {
+        TimeZone { data: &self.data, layout: self.layout }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for ZoneFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ZoneFile").field(&self.time_zone()).finish()
+    }
+}
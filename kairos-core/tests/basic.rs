@@ -1,4 +1,4 @@
-use kairos_core::{ManualClock, Calendar, Clock, VDuration, VInstant};
+use kairos_core::{ManualClock, Calendar, CivilError, Clock, VDuration, VInstant};
  
 #[test]
 fn monotonicidade_manual_clock() {
@@ -94,3 +94,47 @@ fn calendar_to_civil_utc() {
     let (y, m, d, h, ..) = cal.to_civil(t);
     assert_eq!((y, m, d, h), (2025, 1, 1, 12));
 }
+
+#[test]
+fn calendar_display() {
+    let cal = Calendar::new().with_tz_offset_secs(-3 * 3600);
+    let t = VInstant(VDuration::from_secs(12 * 3600 + 34 * 60 + 56).0 + 123_456_789);
+    assert_eq!(cal.display(t).to_string(), "1970-01-01T09:34:56.123-03:00");
+    assert_eq!(cal.display(t).to_string(), cal.format(t).as_str());
+    assert_eq!(format!("{:.9}", cal.display(t)), "1970-01-01T09:34:56.123456789-03:00");
+    assert_eq!(format!("{:.0}", cal.display(t)), "1970-01-01T09:34:56-03:00");
+}
+
+#[test]
+fn calendar_from_civil_round_trip() {
+    let utc = Calendar::new().with_epoch(2025, 1, 1).with_tz_offset_secs(-3 * 3600);
+    let t = utc.from_civil(2025, 3, 1, 21, 5, 9, 123_456_789).unwrap();
+    assert_eq!(t, VInstant(((59 * 86_400 + 24 * 3600 + 5 * 60 + 9) as u64) * 1_000_000_000 + 123_456_789));
+    assert_eq!(utc.to_civil(t), (2025, 3, 1, 21, 5, 9, 123));
+
+    // A local epoch counts wall-clock time: the offset plays no part.
+    let local = Calendar::new().with_epoch_local(2025, 1, 1).with_tz_offset_secs(-3 * 3600);
+    assert_eq!(local.from_civil(2025, 1, 1, 0, 0, 1, 0), Ok(VInstant(1_000_000_000)));
+    assert_eq!(local.to_civil(VInstant(1_000_000_000)), (2025, 1, 1, 0, 0, 1, 0));
+}
+
+#[test]
+fn calendar_from_civil_validation() {
+    let cal = Calendar::new().with_epoch(2024, 1, 1);
+    assert!(cal.from_civil(2024, 2, 29, 0, 0, 0, 0).is_ok());
+    for (y, mo, d, h, mi, s, ns) in [
+        (2023, 2, 29, 0, 0, 0, 0),
+        (2024, 13, 1, 0, 0, 0, 0),
+        (2024, 4, 31, 0, 0, 0, 0),
+        (2024, 1, 0, 0, 0, 0, 0),
+        (2024, 1, 1, 24, 0, 0, 0),
+        (2024, 1, 1, 0, 60, 0, 0),
+        (2024, 1, 1, 0, 0, 60, 0),
+        (2024, 1, 1, 0, 0, 0, 1_000_000_000),
+    ] {
+        assert_eq!(cal.from_civil(y, mo, d, h, mi, s, ns), Err(CivilError::InvalidDateTime));
+    }
+    // Before the epoch, or past the ~584 years a VInstant covers.
+    assert_eq!(cal.from_civil(2023, 12, 31, 23, 59, 59, 0), Err(CivilError::OutOfRange));
+    assert_eq!(cal.from_civil(2700, 1, 1, 0, 0, 0, 0), Err(CivilError::OutOfRange));
+}
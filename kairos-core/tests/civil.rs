@@ -0,0 +1,87 @@
+use kairos_core::{Calendar, CivilDate, CivilDateTime, CivilDays, CivilError, CivilTime, TimeZone, VInstant, Weekday};
+
+fn date(y: i32, m: u32, d: u32) -> CivilDate {
+    CivilDate::new(y, m, d).unwrap()
+}
+
+#[test]
+fn checked_constructors() {
+    assert_eq!(CivilDate::new(2024, 2, 29).map(|d| (d.year(), d.month(), d.day())), Ok((2024, 2, 29)));
+    for (y, m, d) in [(2023, 2, 29), (2024, 4, 31), (2024, 0, 1), (2024, 13, 1), (2024, 1, 0)] {
+        assert_eq!(CivilDate::new(y, m, d), Err(CivilError::InvalidDateTime), "{y}-{m}-{d}");
+    }
+    assert!(CivilTime::new(23, 59, 59, 999_999_999).is_ok());
+    for (h, mi, s, ns) in [(24, 0, 0, 0), (0, 60, 0, 0), (0, 0, 60, 0), (0, 0, 0, 1_000_000_000)] {
+        assert_eq!(CivilTime::new(h, mi, s, ns), Err(CivilError::InvalidDateTime));
+    }
+}
+
+#[test]
+fn date_queries() {
+    let d = date(2024, 3, 15);
+    assert_eq!(d.weekday(), Weekday::Friday);
+    assert_eq!(d.weekday().number_from_monday(), 5);
+    assert_eq!(d.weekday().days_from_sunday(), 5);
+    assert_eq!(Weekday::Sunday.days_from_sunday(), 0);
+    assert_eq!(d.day_of_year(), 75);
+    assert_eq!(d.iso_week(), (2024, 11));
+    assert!(d.is_leap_year());
+    assert_eq!(date(2024, 12, 31).day_of_year(), 366);
+    assert!(!date(1900, 1, 1).is_leap_year());
+    assert!(date(2000, 1, 1).is_leap_year());
+    assert_eq!(date(2023, 2, 1).days_in_month(), 28);
+
+    // ISO weeks straddle year boundaries.
+    assert_eq!(date(2021, 1, 3).iso_week(), (2020, 53));
+    assert_eq!(date(2024, 12, 30).iso_week(), (2025, 1));
+    assert_eq!(date(1970, 1, 1).weekday(), Weekday::Thursday);
+    assert_eq!(date(-1, 12, 31).weekday(), Weekday::Friday);
+}
+
+#[test]
+fn day_numbers() {
+    assert_eq!(date(1970, 1, 1).to_days(), CivilDays::new(0));
+    assert_eq!(date(2024, 3, 15).to_days().get(), 19_797);
+    assert_eq!(CivilDate::from_days(CivilDays::new(-1)), Some(date(1969, 12, 31)));
+    assert_eq!(CivilDate::from_days(CivilDate::MIN.to_days()), Some(CivilDate::MIN));
+    assert_eq!(CivilDate::from_days(CivilDate::MAX.to_days()), Some(CivilDate::MAX));
+    assert_eq!(CivilDate::from_days(CivilDays::new(CivilDate::MAX.to_days().get() + 1)), None);
+    assert_eq!(CivilDate::from_days(CivilDays::new(i64::MIN)), None);
+}
+
+#[test]
+fn ordering() {
+    let noon = CivilTime::new(12, 0, 0, 0).unwrap();
+    let just_after = CivilTime::new(12, 0, 0, 1).unwrap();
+    assert!(date(2023, 12, 31) < date(2024, 1, 1));
+    assert!(date(2024, 1, 31) < date(2024, 2, 1));
+    assert!(CivilTime::MIDNIGHT < noon && noon < just_after);
+    let a = date(2024, 1, 1).and_time(just_after);
+    let b = CivilDateTime::new(date(2024, 1, 2), CivilTime::MIDNIGHT);
+    assert!(a < b);
+    assert_eq!(a.date(), date(2024, 1, 1));
+    assert_eq!(a.time().nanosecond(), 1);
+}
+
+#[test]
+fn through_a_calendar() {
+    let cal = Calendar::new().with_epoch(2024, 1, 1).with_tz_offset_secs(-3 * 3_600);
+    let dt = date(2024, 3, 15).and_time(CivilTime::new(7, 30, 0, 123_456_789).unwrap());
+    let t = cal.from_civil_datetime(dt).unwrap();
+    assert_eq!(t, VInstant((86_400 * 74 + 37_800) * 1_000_000_000 + 123_456_789));
+    // Nanoseconds survive the round trip; `to_civil` keeps only milliseconds.
+    assert_eq!(cal.to_civil_datetime(t), dt);
+    assert_eq!(cal.to_civil(t), (2024, 3, 15, 7, 30, 0, 123));
+    assert_eq!(cal.from_civil(2024, 3, 15, 7, 30, 0, 123_456_789), Ok(t));
+
+    let before_epoch = date(2023, 12, 31).and_time(CivilTime::MIDNIGHT);
+    assert_eq!(cal.from_civil_datetime(before_epoch), Err(CivilError::OutOfRange));
+
+    let berlin = Calendar::new().with_time_zone(TimeZone::from_bytes(include_bytes!("data/Europe_Berlin")).unwrap());
+    let repeated = date(2024, 10, 27).and_time(CivilTime::new(2, 30, 0, 0).unwrap());
+    let Err(CivilError::Ambiguous { earlier, later }) = berlin.from_civil_datetime(repeated) else {
+        panic!("02:30 happened twice");
+    };
+    assert_eq!(berlin.to_civil_datetime(earlier), repeated);
+    assert_eq!(berlin.to_civil_datetime(later), repeated);
+}
@@ -0,0 +1,110 @@
+use kairos_core::{Calendar, CivilError, ParseInstantError, ParseInstantErrorKind, TimeZone, VInstant};
+
+const NS: u64 = 1_000_000_000;
+
+fn at(secs: u64, nanos: u64) -> VInstant {
+    VInstant(secs * NS + nanos)
+}
+
+fn err(position: usize, kind: ParseInstantErrorKind) -> Result<VInstant, ParseInstantError> {
+    Err(ParseInstantError { position, kind })
+}
+
+#[test]
+fn rfc3339_round_trips_format() {
+    let cal = Calendar::new().with_epoch(2024, 1, 1).with_tz_offset_secs(-3 * 3600);
+    let t = at(86_400 * 74 + 37_800, 250_000_000);
+    let text = cal.format(t);
+    assert_eq!(text, "2024-03-15T07:30:00.250-03:00");
+    assert_eq!(cal.parse_rfc3339(&text), Ok(t));
+    assert_eq!(cal.parse_rfc3339("2024-03-15t10:30:00.25z"), Ok(t));
+    assert_eq!(cal.parse_rfc3339("2024-03-15 10:30:00.250000000Z"), Ok(t));
+    assert_eq!(cal.parse_rfc3339("2024-01-01T00:00:00.000000001Z"), Ok(VInstant(1)));
+}
+
+#[test]
+fn rfc3339_is_strict() {
+    let cal = Calendar::new();
+    let expected = |what| ParseInstantErrorKind::Expected(what);
+    assert_eq!(cal.parse_rfc3339(""), err(0, expected("digit")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15"), err(10, expected("'T'")));
+    assert_eq!(cal.parse_rfc3339("20240315T103000Z"), err(4, expected("'-'")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T10:30Z"), err(16, expected("':'")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T10:30:00"), err(19, expected("offset")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T10:30:00+0100"), err(22, expected("':'")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T10:30:00,5Z"), err(19, expected("offset")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T10:30:00.Z"), err(20, expected("digit")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T10:30:00Zulu"), err(20, ParseInstantErrorKind::TrailingCharacters));
+}
+
+#[test]
+fn invalid_fields_point_at_the_field() {
+    let cal = Calendar::new();
+    let invalid = |field| ParseInstantErrorKind::InvalidValue(field);
+    assert_eq!(cal.parse_rfc3339("2024-13-01T00:00:00Z"), err(5, invalid("month")));
+    assert_eq!(cal.parse_rfc3339("2023-02-29T00:00:00Z"), err(8, invalid("day")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T24:00:00Z"), err(11, invalid("hour")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T10:60:00Z"), err(14, invalid("minute")));
+    // Leap seconds cannot be represented.
+    assert_eq!(cal.parse_rfc3339("2016-12-31T23:59:60Z"), err(17, invalid("second")));
+    assert_eq!(cal.parse_rfc3339("2024-03-15T10:30:00+24:00"), err(19, invalid("offset")));
+    assert_eq!(
+        cal.parse_rfc3339("2024-03-15T10:30:00.1234567891Z"),
+        err(29, invalid("fraction (nanoseconds at most)"))
+    );
+    assert_eq!(cal.parse_iso8601("2023-366"), err(5, invalid("day of year")));
+    assert_eq!(cal.parse_iso8601("2021-W53"), err(6, invalid("week")));
+    assert_eq!(cal.parse_iso8601("2020-W01-8"), err(9, invalid("weekday")));
+}
+
+#[test]
+fn iso8601_variants() {
+    let cal = Calendar::new();
+    let t = at(1_710_498_600, 0); // 2024-03-15T10:30:00Z, a Friday
+    for text in [
+        "2024-03-15T10:30:00Z",
+        "20240315T103000Z",
+        "20240315T1030Z",
+        "2024-03-15T10:30Z",
+        "2024-075T10:30:00Z",
+        "2024075T103000Z",
+        "2024-W11-5T10:30:00Z",
+        "2024W115T103000Z",
+        "+002024-03-15T10:30:00Z",
+        "2024-03-15T11:30:00+01",
+        "2024-03-15T05:00:00-0530",
+        "2024-03-15T10:30:00,000Z",
+    ] {
+        assert_eq!(cal.parse_iso8601(text), Ok(t), "{text}");
+    }
+    // Dates alone are midnight; week dates default to Monday.
+    assert_eq!(cal.parse_iso8601("2024-03-15"), Ok(at(1_710_460_800, 0)));
+    assert_eq!(cal.parse_iso8601("2024-W11"), Ok(at(1_710_115_200, 0)));
+    // ISO week 1 of 2021 starts on 2021-01-04; 2020 has 53 weeks.
+    assert_eq!(cal.parse_iso8601("2021-W01-1"), cal.parse_iso8601("2021-01-04"));
+    assert_eq!(cal.parse_iso8601("2020-W53-7"), cal.parse_iso8601("2021-01-03"));
+}
+
+#[test]
+fn placement_on_the_calendar() {
+    // Without an offset the time is read in the calendar's zone.
+    let cal = Calendar::new().with_epoch(2024, 1, 1).with_tz_offset_secs(3_600);
+    assert_eq!(cal.parse_iso8601("2024-01-01T01:00"), Ok(VInstant(0)));
+    assert_eq!(
+        cal.parse_rfc3339("2023-12-31T23:59:59Z"),
+        err(0, ParseInstantErrorKind::Unrepresentable(CivilError::OutOfRange))
+    );
+
+    // A local epoch counts wall-clock time in the calendar's offset.
+    let local = Calendar::new().with_epoch_local(2024, 1, 1).with_tz_offset_secs(3_600);
+    assert_eq!(local.parse_rfc3339("2024-01-01T00:00:00+01:00"), Ok(VInstant(0)));
+    assert_eq!(local.parse_rfc3339("2024-01-01T00:00:00Z"), Ok(at(3_600, 0)));
+
+    let tz = TimeZone::from_bytes(include_bytes!("data/Europe_Berlin")).unwrap();
+    let berlin = Calendar::new().with_time_zone(tz);
+    assert!(matches!(
+        berlin.parse_iso8601("2024-10-27T02:30"),
+        Err(ParseInstantError { kind: ParseInstantErrorKind::Unrepresentable(CivilError::Ambiguous { .. }), .. })
+    ));
+    assert_eq!(berlin.parse_iso8601("2024-10-27T02:30+01:00"), Ok(at(1_729_992_600, 0)));
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "serde")]
+
+use kairos_core::{Calendar, KairosTs, KairosTs16, TimeZone, VDuration, VInstant, VSignedDuration};
+use serde_test::{assert_tokens, Configure, Token};
+
+#[test]
+fn durations_use_the_largest_exact_unit() {
+    for (ns, text) in [
+        (0, "0s"),
+        (1, "1ns"),
+        (1_500, "1500ns"),
+        (250_000_000, "250ms"),
+        (90_000_000_000, "90s"),
+        (3_600_000_000_000, "1h"),
+        (172_800_000_000_000, "2d"),
+    ] {
+        let d = VDuration(ns);
+        assert_eq!(serde_json::to_string(&d).unwrap(), format!("\"{text}\""));
+        assert_eq!(serde_json::from_str::<VDuration>(&format!("\"{text}\"")).unwrap(), d);
+    }
+    assert_eq!(serde_json::from_str::<VDuration>("\"7us\"").unwrap(), VDuration(7_000));
+    assert_eq!(serde_json::from_str::<VDuration>("\"7µs\"").unwrap(), VDuration(7_000));
+    // Bare integers are nanoseconds.
+    assert_eq!(serde_json::from_str::<VDuration>("42").unwrap(), VDuration(42));
+
+    for bad in ["\"\"", "\"ms\"", "\"5\"", "\"5 s\"", "\"5w\"", "\"99999999999d\"", "-1"] {
+        assert!(serde_json::from_str::<VDuration>(bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn signed_durations_carry_a_sign() {
+    for (ns, text) in [(-250_000_000, "-250ms"), (90_000_000_000, "90s"), (i64::MIN, "-9223372036854775808ns")] {
+        let d = VSignedDuration(ns);
+        assert_eq!(serde_json::to_string(&d).unwrap(), format!("\"{text}\""));
+        assert_eq!(serde_json::from_str::<VSignedDuration>(&format!("\"{text}\"")).unwrap(), d);
+    }
+    assert_eq!(serde_json::from_str::<VSignedDuration>("\"+1s\"").unwrap(), VSignedDuration(1_000_000_000));
+    assert_eq!(serde_json::from_str::<VSignedDuration>("-5").unwrap(), VSignedDuration(-5));
+    assert!(serde_json::from_str::<VSignedDuration>("\"9223372036854775808ns\"").is_err());
+    assert_tokens(&VSignedDuration(-3).compact(), &[Token::I64(-3)]);
+}
+
+#[test]
+fn instants_are_rfc3339_utc() {
+    let t = VInstant(1_700_000_000_123_000_000);
+    assert_eq!(serde_json::to_string(&t).unwrap(), "\"2023-11-14T22:13:20.123Z\"");
+    for (ns, text) in [
+        (0, "1970-01-01T00:00:00Z"),
+        (1_000, "1970-01-01T00:00:00.000001Z"),
+        (951_782_400_000_000_007, "2000-02-29T00:00:00.000000007Z"),
+        (u64::MAX, "2554-07-21T23:34:33.709551615Z"),
+    ] {
+        assert_eq!(serde_json::to_string(&VInstant(ns)).unwrap(), format!("\"{text}\""));
+        assert_eq!(serde_json::from_str::<VInstant>(&format!("\"{text}\"")).unwrap(), VInstant(ns));
+    }
+
+    // Offsets and lower-case separators are accepted on input.
+    let parsed: VInstant = serde_json::from_str("\"2023-11-14t19:13:20.123-03:00\"").unwrap();
+    assert_eq!(parsed, t);
+    assert_eq!(serde_json::from_str::<VInstant>("12").unwrap(), VInstant(12));
+
+    for bad in [
+        "\"1969-12-31T23:59:59Z\"",
+        "\"2023-02-29T00:00:00Z\"",
+        "\"2023-11-14T24:00:00Z\"",
+        "\"2023-11-14T22:13:20\"",
+        "\"2023-11-14T22:13:20.1234567890Z\"",
+        "\"2600-01-01T00:00:00Z\"",
+    ] {
+        assert!(serde_json::from_str::<VInstant>(bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn compact_forms_are_integers() {
+    assert_tokens(&VDuration(1_500).compact(), &[Token::U64(1_500)]);
+    assert_tokens(&VInstant(7).compact(), &[Token::U64(7)]);
+    assert_tokens(&VDuration(1_500).readable(), &[Token::Str("1500ns")]);
+
+    let ts = KairosTs { phys_ns: 5, log: 2, node: 9 };
+    assert_tokens(
+        &ts.compact(),
+        &[Token::Tuple { len: 3 }, Token::U64(5), Token::U32(2), Token::U32(9), Token::TupleEnd],
+    );
+    assert_tokens(
+        &Calendar::new().with_epoch(2000, 1, 1).with_tz_offset_secs(-10_800).compact(),
+        &[Token::Tuple { len: 3 }, Token::I64(10_957), Token::I32(-10_800), Token::Bool(false), Token::TupleEnd],
+    );
+}
+
+#[test]
+fn hlc_timestamps_round_trip() {
+    let ts = KairosTs { phys_ns: 1_000_000_000, log: 3, node: 7 };
+    let json = serde_json::to_string(&ts).unwrap();
+    assert_eq!(json, r#"{"phys":"1970-01-01T00:00:01Z","log":3,"node":7}"#);
+    assert_eq!(serde_json::from_str::<KairosTs>(&json).unwrap(), ts);
+
+    let wide: KairosTs16 = serde_json::from_str(r#"{"node":7,"log":3,"phys":1000000000}"#).unwrap();
+    assert_eq!(KairosTs::from(wide), ts);
+    assert_eq!(serde_json::to_string(&KairosTs16::from(ts)).unwrap(), json);
+
+    assert!(serde_json::from_str::<KairosTs>(r#"{"phys":0,"log":3}"#).is_err());
+    assert!(serde_json::from_str::<KairosTs>(r#"{"phys":0,"log":3,"node":1,"log":4}"#).is_err());
+}
+
+#[test]
+fn calendars_round_trip() {
+    let cal = Calendar::new().with_epoch_local(2024, 2, 29).with_tz_offset_secs(-(3 * 3600 + 30 * 60));
+    let json = serde_json::to_string(&cal).unwrap();
+    assert_eq!(json, r#"{"epoch":"2024-02-29","tz_offset":"-03:30","anchor":"local"}"#);
+    assert_eq!(serde_json::from_str::<Calendar>(&json).unwrap(), cal);
+
+    let odd = Calendar::new().with_epoch(-44, 3, 15).with_tz_offset_secs(45);
+    let json = serde_json::to_string(&odd).unwrap();
+    assert_eq!(json, r#"{"epoch":"-0044-03-15","tz_offset":"+00:00:45","anchor":"utc"}"#);
+    assert_eq!(serde_json::from_str::<Calendar>(&json).unwrap(), odd);
+
+    // Missing fields fall back to the default calendar.
+    assert_eq!(serde_json::from_str::<Calendar>("{}").unwrap(), Calendar::default());
+    assert!(serde_json::from_str::<Calendar>(r#"{"epoch":"2023-02-30"}"#).is_err());
+    assert!(serde_json::from_str::<Calendar>(r#"{"anchor":"mars"}"#).is_err());
+}
+
+#[test]
+fn calendars_with_a_zone_are_not_serialized() {
+    let tz = TimeZone::from_bytes(include_bytes!("data/Europe_Berlin")).unwrap();
+    assert!(serde_json::to_string(&Calendar::new().with_time_zone(tz)).is_err());
+}
@@ -0,0 +1,90 @@
+use kairos_core::{Calendar, TimeZone, VInstant};
+
+const NS: u64 = 1_000_000_000;
+
+fn at(secs: u64, nanos: u64) -> VInstant {
+    VInstant(secs * NS + nanos)
+}
+
+#[test]
+fn numeric_fields() {
+    let cal = Calendar::new();
+    let t = at(1_709_620_200, 250_000_000); // 2024-03-05T06:30:00.25Z, a Tuesday
+    let f = |pattern| cal.strftime(t, pattern).to_string();
+    assert_eq!(f("%Y-%m-%d %H:%M:%S%.9f %z"), "2024-03-05 06:30:00.250000000 +0000");
+    assert_eq!(f("%F %T"), "2024-03-05 06:30:00");
+    assert_eq!(f("%C|%y|%e|%-d|%_m|%k|%I|%l|%p|%P"), "20|24| 5|5| 3| 6|06| 6|AM|am");
+    assert_eq!(f("%j %u %w %U %W %G-W%V %g"), "065 2 2 09 10 2024-W10 24");
+    assert_eq!(f("%s"), "1709620200");
+    assert_eq!(f("%D %R %x %X"), "03/05/24 06:30 03/05/24 06:30:00");
+    assert_eq!(f("%%|%n|%t"), "%|\n|\t");
+}
+
+#[test]
+fn names() {
+    let cal = Calendar::new();
+    let t = at(1_709_620_200, 0);
+    assert_eq!(cal.strftime(t, "%a %A %b %h %B").to_string(), "Tue Tuesday Mar Mar March");
+    assert_eq!(cal.strftime(t, "%c").to_string(), "Tue Mar  5 06:30:00 2024");
+    assert_eq!(cal.strftime(at(0, 0), "%A, %-d %B %Y").to_string(), "Thursday, 1 January 1970");
+}
+
+#[test]
+fn fractions() {
+    let cal = Calendar::new();
+    let f = |nanos, pattern| cal.strftime(at(0, nanos), pattern).to_string();
+    assert_eq!(f(123_456_789, "%f"), "123456789");
+    assert_eq!(f(123_456_789, "%.3f|%3f|%.1f|%6f"), ".123|123|.1|123456");
+    assert_eq!(f(0, "%S%.f"), "00");
+    assert_eq!(f(250_000_000, "%.f"), ".250");
+    assert_eq!(f(250_500_000, "%.f"), ".250500");
+    assert_eq!(f(1, "%.f"), ".000000001");
+    assert_eq!(f(1, "%+"), "1970-01-01T00:00:00.000000001+00:00");
+}
+
+#[test]
+fn iso_weeks_at_year_boundaries() {
+    let cal = Calendar::new();
+    let day = |y, m, d| cal.from_civil(y, m, d, 12, 0, 0, 0).unwrap();
+    // 2021-01-03 was a Sunday in week 53 of 2020; 2024-12-30 is in week 1 of 2025.
+    assert_eq!(cal.strftime(day(2021, 1, 3), "%G-W%V-%u %U %W").to_string(), "2020-W53-7 01 00");
+    assert_eq!(cal.strftime(day(2024, 12, 30), "%G-W%V-%u %j").to_string(), "2025-W01-1 365");
+    assert_eq!(cal.strftime(day(2024, 12, 31), "%j").to_string(), "366");
+}
+
+#[test]
+fn offsets_and_zones() {
+    let t = at(1_709_620_200, 0);
+    let fixed = Calendar::new().with_tz_offset_secs(-(5 * 3_600 + 30 * 60));
+    assert_eq!(fixed.strftime(t, "%H:%M %z %:z %Z").to_string(), "01:00 -0530 -05:30 -05:30");
+
+    let tz = TimeZone::from_bytes(include_bytes!("data/Europe_Berlin")).unwrap();
+    let berlin = Calendar::new().with_time_zone(tz);
+    assert_eq!(berlin.strftime(t, "%H:%M %Z %z").to_string(), "07:30 CET +0100");
+    assert_eq!(berlin.strftime(at(1_719_828_000, 0), "%H:%M %Z %z").to_string(), "12:00 CEST +0200");
+    // `%s` stays UTC.
+    assert_eq!(berlin.strftime(t, "%s").to_string(), "1709620200");
+
+    let local = Calendar::new().with_epoch_local(2024, 10, 27).with_time_zone(tz);
+    let wall = local.from_civil(2024, 10, 27, 2, 30, 0, 0).unwrap();
+    assert_eq!(local.strftime(wall, "%T %Z %s").to_string(), "02:30:00 CEST 1729989000");
+}
+
+#[test]
+fn unknown_conversions_are_written_verbatim() {
+    let cal = Calendar::new();
+    assert_eq!(cal.strftime(at(0, 0), "%Q %:Y %.3d %é 100%").to_string(), "%Q %:Y %.3d %é 100%");
+}
+
+#[test]
+fn writes_into_any_fmt_write() {
+    let cal = Calendar::new();
+    let pattern = "%A %d %B %Y, %H:%M:%S%.9f %z (day %j, week %V)";
+    let mut out = heapless::String::<80>::new();
+    cal.format_to(&mut out, at(1_709_620_200, 5), pattern).unwrap();
+    assert_eq!(out, "Tuesday 05 March 2024, 06:30:00.000000005 +0000 (day 065, week 10)");
+
+    // A full buffer is reported, not silently truncated.
+    let mut small = heapless::String::<8>::new();
+    assert!(cal.format_to(&mut small, at(0, 0), pattern).is_err());
+}
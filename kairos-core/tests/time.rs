@@ -1,4 +1,4 @@
-use kairos_core::{VDuration, VInstant};
+use kairos_core::{ParseDurationError, VDuration, VInstant, VSignedDuration};
 
 #[test]
 fn vduration_from_micros() {
@@ -57,3 +57,213 @@ fn vinstant_from_vduration() {
     let t = VInstant::from(d);
     assert_eq!(t.0, 5_000_000_000);
 }
+
+#[test]
+fn vduration_mul_div_by_integer() {
+    let d = VDuration::from_millis(250);
+    assert_eq!(d * 4, VDuration::from_secs(1));
+    assert_eq!(4 * d, VDuration::from_secs(1));
+    assert_eq!(d / 2, VDuration::from_micros(125_000));
+    assert_eq!(VDuration::MAX * 2, VDuration::MAX);
+
+    let mut e = d;
+    e *= 3;
+    e /= 5;
+    assert_eq!(e, VDuration::from_millis(150));
+    e -= VDuration::from_secs(1);
+    assert_eq!(e, VDuration::ZERO);
+}
+
+#[test]
+fn vduration_ratio_and_remainder() {
+    let period = VDuration::from_millis(300);
+    let elapsed = VDuration::from_secs(1);
+    assert_eq!(elapsed / period, 3);
+    assert_eq!(elapsed % period, VDuration::from_millis(100));
+}
+
+#[test]
+fn vduration_checked_saturating_wrapping() {
+    let one = VDuration(1);
+    assert_eq!(VDuration::MAX.checked_add(one), None);
+    assert_eq!(VDuration::ZERO.checked_sub(one), None);
+    assert_eq!(VDuration::MAX.checked_mul(2), None);
+    assert_eq!(one.checked_div(0), None);
+    assert_eq!(VDuration(10).checked_div(3), Some(VDuration(3)));
+
+    assert_eq!(VDuration::MAX.saturating_add(one), VDuration::MAX);
+    assert_eq!(VDuration::ZERO.saturating_sub(one), VDuration::ZERO);
+    assert_eq!(VDuration::MAX.saturating_mul(3), VDuration::MAX);
+
+    assert_eq!(VDuration::MAX.wrapping_add(one), VDuration::ZERO);
+    assert_eq!(VDuration::ZERO.wrapping_sub(one), VDuration::MAX);
+    assert_eq!(VDuration(1 << 63).wrapping_mul(2), VDuration::ZERO);
+}
+
+#[test]
+fn vduration_unit_accessors() {
+    let d = VDuration(3_723_456_789_012);
+    assert_eq!(d.as_secs(), 3_723);
+    assert_eq!(d.as_millis(), 3_723_456);
+    assert_eq!(d.as_micros(), 3_723_456_789);
+    assert_eq!(d.subsec_nanos(), 456_789_012);
+    assert_eq!(d.subsec_micros(), 456_789);
+    assert_eq!(d.subsec_millis(), 456);
+    assert!(VDuration::ZERO.is_zero());
+    assert!(VDuration::from_millis(999) < VDuration::from_secs(1));
+}
+
+#[test]
+fn vduration_float_conversions() {
+    assert_eq!(VDuration::from_secs_f64(1.5), VDuration::from_millis(1500));
+    assert_eq!(VDuration::from_secs_f64(1e-9 * 0.6), VDuration(1));
+    assert_eq!(VDuration::from_secs_f64(-1.0), VDuration::ZERO);
+    assert_eq!(VDuration::from_secs_f64(f64::NAN), VDuration::ZERO);
+    assert_eq!(VDuration::from_secs_f64(1e12), VDuration::MAX);
+
+    assert_eq!(VDuration::from_secs(2).mul_f64(0.25), VDuration::from_millis(500));
+    assert_eq!(VDuration::from_secs(2).mul_f64(-3.0), VDuration::ZERO);
+}
+
+#[test]
+fn vduration_sum() {
+    let parts = [VDuration::from_millis(200), VDuration::from_millis(300), VDuration::from_millis(500)];
+    assert_eq!(parts.iter().sum::<VDuration>(), VDuration::from_secs(1));
+    assert_eq!(parts.into_iter().sum::<VDuration>(), VDuration::from_secs(1));
+    assert_eq!([VDuration::MAX, VDuration(1)].into_iter().sum::<VDuration>(), VDuration::MAX);
+}
+
+#[test]
+fn vduration_into_core_duration() {
+    let d = VDuration(1_500_000_001);
+    // `TryFrom` comes with the lossless `From` and never fails.
+    fn convert<T: TryFrom<VDuration>>(d: VDuration) -> Option<T> {
+        T::try_from(d).ok()
+    }
+    let core_dur: core::time::Duration = convert(d).unwrap();
+    assert_eq!(core_dur, core::time::Duration::new(1, 500_000_001));
+    assert_eq!(VDuration::from(core_dur), d);
+    assert_eq!(core::time::Duration::from(VDuration::MAX).as_nanos(), u128::from(u64::MAX));
+}
+
+#[test]
+fn vinstant_signed_duration_since() {
+    let (early, late) = (VInstant(1_000), VInstant(4_000));
+    assert_eq!(late.signed_duration_since(early), VSignedDuration(3_000));
+    assert_eq!(early.signed_duration_since(late), VSignedDuration(-3_000));
+    assert_eq!(VInstant(u64::MAX).signed_duration_since(VInstant(0)), VSignedDuration::MAX);
+    assert_eq!(VInstant(0).signed_duration_since(VInstant(u64::MAX)), VSignedDuration::MIN);
+}
+
+#[test]
+fn vinstant_checked_and_saturating_since() {
+    let (early, late) = (VInstant(1_000), VInstant(4_000));
+    assert_eq!(late.checked_duration_since(early), Some(VDuration(3_000)));
+    assert_eq!(early.checked_duration_since(late), None);
+    assert_eq!(early.saturating_duration_since(late), VDuration::ZERO);
+    // Plain subtraction keeps wrapping.
+    assert_eq!(early - late, VDuration(u64::MAX - 2_999));
+}
+
+#[test]
+fn vinstant_minus_vduration() {
+    let mut t = VInstant(5_000);
+    assert_eq!(t - VDuration(2_000), VInstant(3_000));
+    t -= VDuration(5_000);
+    assert_eq!(t, VInstant(0));
+
+    assert_eq!(t.checked_sub(VDuration(1)), None);
+    assert_eq!(VInstant(u64::MAX).checked_add(VDuration(1)), None);
+    assert_eq!(VInstant(10).checked_add(VDuration(5)), Some(VInstant(15)));
+    assert_eq!(VInstant(10).checked_add_signed(VSignedDuration(-4)), Some(VInstant(6)));
+    assert_eq!(VInstant(10).checked_add_signed(VSignedDuration(-11)), None);
+}
+
+#[test]
+fn vsigned_duration_neg_and_abs() {
+    let d = VSignedDuration(-1_500);
+    assert_eq!(-d, VSignedDuration(1_500));
+    assert_eq!(d.abs(), VDuration(1_500));
+    assert_eq!((-d).abs(), d.abs());
+    assert_eq!(d.signum(), -1);
+    assert!(d.is_negative() && !d.is_positive());
+
+    // `abs` is exact at the edge; negation saturates.
+    assert_eq!(VSignedDuration::MIN.abs(), VDuration(1 << 63));
+    assert_eq!(-VSignedDuration::MIN, VSignedDuration::MAX);
+    assert_eq!(VSignedDuration::MIN.checked_neg(), None);
+}
+
+#[test]
+fn vsigned_duration_arithmetic_and_conversions() {
+    let samples = [VSignedDuration(-300), VSignedDuration(500), VSignedDuration(-100)];
+    assert_eq!(samples.into_iter().sum::<VSignedDuration>(), VSignedDuration(100));
+    assert_eq!(VSignedDuration::MAX + VSignedDuration(1), VSignedDuration::MAX);
+    assert_eq!(VSignedDuration::MIN - VSignedDuration(1), VSignedDuration::MIN);
+    assert_eq!(VSignedDuration::MAX.checked_add(VSignedDuration(1)), None);
+
+    assert_eq!(VSignedDuration::try_from(VDuration(7)), Ok(VSignedDuration(7)));
+    assert!(VSignedDuration::try_from(VDuration::MAX).is_err());
+    assert_eq!(VDuration::try_from(VSignedDuration(7)), Ok(VDuration(7)));
+    assert!(VDuration::try_from(VSignedDuration(-7)).is_err());
+    assert_eq!(VSignedDuration(-7).to_unsigned(), None);
+}
+
+#[test]
+fn vduration_display() {
+    let cases = [
+        (0, "0s"),
+        (7, "7ns"),
+        (1_500, "1.5us"),
+        (250_000_000, "250ms"),
+        (1_500_000_000, "1.5s"),
+        (90_000_000_000, "1m30s"),
+        (10_920_000_000_000, "3h2m"),
+        (86_400_000_000_001, "1d0.000000001s"),
+        (u64::MAX, "213503d23h34m33.709551615s"),
+    ];
+    for (ns, text) in cases {
+        assert_eq!(VDuration(ns).to_string(), text);
+        assert_eq!(text.parse::<VDuration>(), Ok(VDuration(ns)), "{text}");
+    }
+    assert_eq!(format!("[{:>7}]", VDuration::from_millis(5)), "[    5ms]");
+    assert_eq!(VSignedDuration(-1_500_000_000).to_string(), "-1.5s");
+    assert_eq!(VSignedDuration::MIN.to_string().parse::<VSignedDuration>(), Ok(VSignedDuration::MIN));
+}
+
+#[test]
+fn vduration_parse_compound() {
+    assert_eq!("1h30m".parse::<VDuration>(), Ok(VDuration::from_secs(5_400)));
+    assert_eq!("2d1h0.5m".parse::<VDuration>(), Ok(VDuration::from_secs(2 * 86_400 + 3_600 + 30)));
+    assert_eq!("1.25ms".parse::<VDuration>(), Ok(VDuration::from_micros(1_250)));
+    assert_eq!("7µs".parse::<VDuration>(), Ok(VDuration::from_micros(7)));
+    assert_eq!(".5s".parse::<VDuration>(), Ok(VDuration::from_millis(500)));
+    assert_eq!("1.9ns".parse::<VDuration>(), Ok(VDuration(1)));
+    assert_eq!("0".parse::<VDuration>(), Ok(VDuration::ZERO));
+    assert_eq!("-3m".parse::<VSignedDuration>(), Ok(VSignedDuration(-180_000_000_000)));
+}
+
+#[test]
+fn vduration_parse_errors() {
+    assert_eq!("".parse::<VDuration>(), Err(ParseDurationError::Empty));
+    assert_eq!("5".parse::<VDuration>(), Err(ParseDurationError::MissingUnit(1)));
+    assert_eq!("1h30".parse::<VDuration>(), Err(ParseDurationError::MissingUnit(4)));
+    assert_eq!("ms".parse::<VDuration>(), Err(ParseDurationError::InvalidNumber(0)));
+    assert_eq!("1h.m".parse::<VDuration>(), Err(ParseDurationError::InvalidNumber(2)));
+    assert_eq!("5w".parse::<VDuration>(), Err(ParseDurationError::UnknownUnit(1)));
+    assert_eq!("1 s".parse::<VDuration>(), Err(ParseDurationError::UnknownUnit(1)));
+    assert_eq!("-1s".parse::<VDuration>(), Err(ParseDurationError::InvalidNumber(0)));
+    assert_eq!("+5w".parse::<VSignedDuration>(), Err(ParseDurationError::UnknownUnit(2)));
+    assert_eq!("213504d".parse::<VDuration>(), Err(ParseDurationError::Overflow));
+    assert_eq!("9223372036854775808ns".parse::<VSignedDuration>(), Err(ParseDurationError::Overflow));
+}
+
+#[test]
+fn vinstant_display_raw() {
+    let t = VInstant(12_000_250_000);
+    assert_eq!(t.to_string(), "12.000250000s");
+    assert_eq!(format!("{t:.3}"), "12.000s");
+    assert_eq!(format!("{t:.0}"), "12s");
+    assert_eq!(format!("{t:>15.4}"), "       12.0002s");
+    assert_eq!(VInstant(u64::MAX).to_string(), "18446744073.709551615s");
+}
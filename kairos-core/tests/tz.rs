@@ -0,0 +1,158 @@
+use kairos_core::{Calendar, CivilError, TimeZone, TzError, VDuration, VInstant};
+
+const BERLIN: &[u8] = include_bytes!("data/Europe_Berlin");
+const NEW_YORK: &[u8] = include_bytes!("data/America_New_York");
+const SYDNEY: &[u8] = include_bytes!("data/Australia_Sydney");
+
+/// A version 2 file with no transitions: everything comes from the `footer` TZ string.
+fn footer_only(footer: &str) -> Vec<u8> {
+    let block = |out: &mut Vec<u8>| {
+        out.extend_from_slice(b"TZif2");
+        out.extend_from_slice(&[0; 15]);
+        for count in [0u32, 0, 0, 0, 1, 4] {
+            out.extend_from_slice(&count.to_be_bytes());
+        }
+        out.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+        out.extend_from_slice(b"UTC\0");
+    };
+    let mut data = Vec::new();
+    block(&mut data);
+    block(&mut data);
+    data.extend_from_slice(format!("\n{footer}\n").as_bytes());
+    data
+}
+
+#[test]
+fn berlin_dst_transitions() {
+    let tz = TimeZone::from_bytes(BERLIN).unwrap();
+    // 2024: clocks went forward at 01:00 UTC on March 31 and back on October 27.
+    let at = |secs| {
+        let o = tz.offset_at(secs);
+        (o.utc_offset, o.is_dst, o.abbreviation)
+    };
+    assert_eq!(at(1_711_846_799), (3_600, false, "CET"));
+    assert_eq!(at(1_711_846_800), (7_200, true, "CEST"));
+    assert_eq!(at(1_729_990_799), (7_200, true, "CEST"));
+    assert_eq!(at(1_729_990_800), (3_600, false, "CET"));
+}
+
+#[test]
+fn berlin_history_and_far_future() {
+    let tz = TimeZone::from_bytes(BERLIN).unwrap();
+    // Local mean time before the first transition.
+    assert_eq!(tz.offset_at(-3_000_000_000).utc_offset, 3_208);
+    assert_eq!(tz.offset_at(-3_000_000_000).abbreviation, "LMT");
+    // 1947 had a "midsummer" time three hours ahead of UTC.
+    assert_eq!(tz.offset_at(-714_610_800).abbreviation, "CEMT");
+    assert_eq!(tz.offset_at(-714_610_800).utc_offset, 10_800);
+    // Past the table, the footer rule takes over.
+    assert_eq!(tz.offset_at(4_118_000_000).abbreviation, "CEST");
+    assert_eq!(tz.offset_at(4_118_000_000 + 180 * 86_400).abbreviation, "CET");
+}
+
+#[test]
+fn southern_hemisphere_footer() {
+    let tz = TimeZone::from_bytes(SYDNEY).unwrap();
+    assert_eq!(tz.offset_at(2_524_608_000).abbreviation, "AEDT");
+    assert_eq!(tz.offset_at(2_524_608_000).utc_offset, 11 * 3_600);
+    assert_eq!(tz.offset_at(2_540_000_000).abbreviation, "AEST");
+}
+
+#[test]
+fn posix_rules_without_a_table() {
+    // No explicit rule: DST follows the US dates.
+    let data = footer_only("EST5EDT");
+    let tz = TimeZone::from_bytes(&data).unwrap();
+    assert_eq!(tz.offset_at(1_615_705_199).abbreviation, "EST");
+    assert_eq!(tz.offset_at(1_615_705_200).abbreviation, "EDT");
+    assert_eq!(tz.offset_at(1_615_705_200).utc_offset, -4 * 3_600);
+
+    let data = footer_only("<+0330>-3:30");
+    let tz = TimeZone::from_bytes(&data).unwrap();
+    assert_eq!(tz.offset_at(0).utc_offset, 12_600);
+    assert_eq!(tz.offset_at(0).abbreviation, "+0330");
+
+    // Julian days and times past midnight (RFC 8536 extensions).
+    let data = footer_only("IST-2IDT,M3.4.4/26,M10.5.0");
+    assert!(TimeZone::from_bytes(&data).is_ok());
+    let data = footer_only("XXX3YYY,J60/-1,300/167");
+    assert!(TimeZone::from_bytes(&data).is_ok());
+}
+
+#[test]
+fn rejects_malformed_data() {
+    assert_eq!(TimeZone::from_bytes(b"").unwrap_err(), TzError::NotTzif);
+    assert_eq!(TimeZone::from_bytes(b"PK\x03\x04").unwrap_err(), TzError::NotTzif);
+    assert_eq!(TimeZone::from_bytes(&BERLIN[..100]).unwrap_err(), TzError::Truncated);
+    assert_eq!(TimeZone::from_bytes(&BERLIN[..BERLIN.len() - 1]).unwrap_err(), TzError::InvalidFooter);
+    for bad in ["EST", "CET-1CEST,M3.5.0", "CET-1CEST,M13.5.0,M10.5.0", "E5", "<>5", "CET-25", "XXX3YYY,J60,300/168"] {
+        assert_eq!(TimeZone::from_bytes(&footer_only(bad)).unwrap_err(), TzError::InvalidFooter, "{bad}");
+    }
+}
+
+#[test]
+fn calendar_follows_the_zone() {
+    let tz = TimeZone::from_bytes(NEW_YORK).unwrap();
+    let cal = Calendar::new().with_time_zone(tz);
+    let t = |secs| VInstant(VDuration::from_secs(secs).0);
+
+    assert_eq!(cal.format(t(1_710_053_999)), "2024-03-10T01:59:59.000-05:00");
+    assert_eq!(cal.format(t(1_710_054_000)), "2024-03-10T03:00:00.000-04:00");
+    assert_eq!(cal.to_civil(t(1_730_613_599)), (2024, 11, 3, 1, 59, 59, 0));
+    assert_eq!(cal.to_civil(t(1_730_613_600)), (2024, 11, 3, 1, 0, 0, 0));
+    assert_eq!(cal.utc_offset_at(t(1_730_613_600)), -5 * 3_600);
+
+    // A fixed offset replaces the zone again.
+    assert_eq!(cal.with_tz_offset_secs(0).time_zone(), None);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn zone_files_from_disk() {
+    use kairos_core::ZoneFile;
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/Europe_Berlin");
+    let file = ZoneFile::load_path(path).unwrap();
+    assert_eq!(file.time_zone(), TimeZone::from_bytes(BERLIN).unwrap());
+
+    for name in ["", "/etc/passwd", "../etc/passwd", "Europe//Berlin"] {
+        assert_eq!(ZoneFile::load(name).unwrap_err().kind(), std::io::ErrorKind::InvalidInput, "{name}");
+    }
+    let not_tzif = ZoneFile::load_path(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap_err();
+    assert_eq!(not_tzif.kind(), std::io::ErrorKind::InvalidData);
+
+    // The system database is optional in build environments.
+    if std::path::Path::new("/usr/share/zoneinfo/UTC").exists() && std::env::var_os("TZDIR").is_none() {
+        let utc = ZoneFile::load("UTC").unwrap();
+        assert_eq!(utc.time_zone().offset_at(0).utc_offset, 0);
+    }
+}
+
+#[test]
+fn from_civil_in_a_zone() {
+    let berlin = Calendar::new().with_time_zone(TimeZone::from_bytes(BERLIN).unwrap());
+    let t = |secs: u64| VInstant(secs * 1_000_000_000);
+
+    // Summer: CEST, two hours ahead of UTC.
+    assert_eq!(berlin.from_civil(2024, 7, 1, 12, 0, 0, 0), Ok(t(1_719_828_000)));
+    // 02:30 on 2024-03-31 was skipped; the readings with CEST and CET straddle 01:00 UTC.
+    assert_eq!(
+        berlin.from_civil(2024, 3, 31, 2, 30, 0, 0),
+        Err(CivilError::Nonexistent { earlier: t(1_711_845_000), later: t(1_711_848_600) })
+    );
+    // 02:30 on 2024-10-27 happened twice, in CEST and then in CET.
+    assert_eq!(
+        berlin.from_civil(2024, 10, 27, 2, 30, 0, 0),
+        Err(CivilError::Ambiguous { earlier: t(1_729_989_000), later: t(1_729_992_600) })
+    );
+    for secs in [1_729_989_000, 1_729_992_600] {
+        assert_eq!(berlin.to_civil(t(secs)), (2024, 10, 27, 2, 30, 0, 0));
+    }
+    // Either side of the change is unambiguous.
+    assert_eq!(berlin.from_civil(2024, 10, 27, 3, 0, 0, 0), Ok(t(1_729_994_400)));
+
+    // With a local epoch, the offset shown for a repeated hour is the earlier one.
+    let local = Calendar::new().with_epoch_local(2024, 10, 27).with_time_zone(TimeZone::from_bytes(BERLIN).unwrap());
+    let wall = local.from_civil(2024, 10, 27, 2, 30, 0, 0).unwrap();
+    assert_eq!(local.format(wall), "2024-10-27T02:30:00.000+02:00");
+}
@@ -0,0 +1,123 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use kairos_core::{ManualClock, VDuration};
+use kairos_scheduler::storage::PqStorage;
+use kairos_scheduler::{Scheduler, Storage};
+
+#[cfg(feature = "timer-wheel")]
+use kairos_scheduler::storage::WheelStorage;
+#[cfg(feature = "calendar-queue")]
+use kairos_scheduler::storage::CalendarQueueStorage;
+
+const EVENTS: u64 = 10_000;
+/// Pending population for the hold model.
+const HELD: u64 = 200_000;
+
+/// Network-style timeouts: schedule a batch of short deadlines, cancel most of them,
+/// then drain whatever is left.
+fn timeouts<S: Storage<u64>>(mut scheduler: Scheduler<u64, ManualClock, S>) -> u64 {
+    let mut ids = Vec::with_capacity(EVENTS as usize);
+    for i in 0..EVENTS {
+        let dur = VDuration::from_micros(100 + (i * 7_919) % 50_000);
+        ids.push(scheduler.schedule_in(dur, i).unwrap());
+    }
+    for (i, id) in ids.into_iter().enumerate() {
+        if i % 10 != 0 {
+            let _ = scheduler.cancel(id);
+        }
+    }
+    let mut sum = 0;
+    let _ = scheduler.run_until_idle(usize::MAX, |_, payload| sum += payload);
+    sum
+}
+
+/// Every event fires: schedule a batch spread over a second, then drain it.
+fn drain<S: Storage<u64>>(mut scheduler: Scheduler<u64, ManualClock, S>) -> u64 {
+    for i in 0..EVENTS {
+        let dur = VDuration::from_micros((i * 7_919) % 1_000_000);
+        let _ = scheduler.schedule_in(dur, i).unwrap();
+    }
+    let mut sum = 0;
+    let _ = scheduler.run_until_idle(usize::MAX, |_, payload| sum += payload);
+    sum
+}
+
+/// Classic "hold" model of discrete-event simulation: `HELD` events stay pending while
+/// each firing schedules its successor a pseudo-random delay later.
+fn populate<S: Storage<u64>>(storage: S) -> Scheduler<u64, ManualClock, S> {
+    let mut scheduler = Scheduler::with_storage(ManualClock::new(), storage);
+    for i in 0..HELD {
+        let _ = scheduler.schedule_in(VDuration::from_nanos((i * 7_919) % 1_000_000_000), i).unwrap();
+    }
+    scheduler
+}
+
+fn hold<S: Storage<u64>>(scheduler: &mut Scheduler<u64, ManualClock, S>) {
+    let event = scheduler.step().unwrap();
+    let delay = (event.payload.wrapping_mul(6_364_136_223_846_793_005) >> 34) % 1_000_000_000;
+    let _ = scheduler.schedule_in(VDuration::from_nanos(delay), event.payload + 1).unwrap();
+}
+
+fn bench_hold(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hold");
+
+    let mut pq = populate(PqStorage::new());
+    group.bench_function("pq_hold_200k", |b| b.iter(|| hold(&mut pq)));
+
+    #[cfg(feature = "calendar-queue")]
+    {
+        let mut cq = populate(CalendarQueueStorage::new());
+        group.bench_function("calendar_queue_hold_200k", |b| b.iter(|| hold(&mut cq)));
+    }
+
+    group.finish();
+}
+
+fn bench_storage(c: &mut Criterion) {
+    let mut group = c.benchmark_group("storage");
+
+    group.bench_function("pq_timeouts_90%_cancelled", |b| {
+        b.iter_batched(
+            || Scheduler::with_storage(ManualClock::new(), PqStorage::new()),
+            |s| std::hint::black_box(timeouts(s)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("pq_drain", |b| {
+        b.iter_batched(
+            || Scheduler::with_storage(ManualClock::new(), PqStorage::new()),
+            |s| std::hint::black_box(drain(s)),
+            BatchSize::LargeInput,
+        )
+    });
+
+    #[cfg(feature = "timer-wheel")]
+    group.bench_function("wheel_timeouts_90%_cancelled", |b| {
+        b.iter_batched(
+            || Scheduler::with_storage(ManualClock::new(), WheelStorage::new()),
+            |s| std::hint::black_box(timeouts(s)),
+            BatchSize::LargeInput,
+        )
+    });
+    #[cfg(feature = "timer-wheel")]
+    group.bench_function("wheel_drain", |b| {
+        b.iter_batched(
+            || Scheduler::with_storage(ManualClock::new(), WheelStorage::new()),
+            |s| std::hint::black_box(drain(s)),
+            BatchSize::LargeInput,
+        )
+    });
+
+    #[cfg(feature = "calendar-queue")]
+    group.bench_function("calendar_queue_drain", |b| {
+        b.iter_batched(
+            || Scheduler::with_storage(ManualClock::new(), CalendarQueueStorage::new()),
+            |s| std::hint::black_box(drain(s)),
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_storage, bench_hold);
+criterion_main!(benches);
@@ -0,0 +1,216 @@
+// kairos-scheduler/src/codec.rs
+
+use core::fmt;
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+
+/// Bytes handed to a decoder were truncated or malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError {
+    /// Byte offset at which decoding failed.
+    pub offset: usize,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "malformed data at byte {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// A fixed-capacity output buffer ran out of room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncodeError;
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("output buffer is full")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+/// `v` as an unsigned LEB128 varint: the buffer and how many bytes of it are used.
+fn varint(mut v: u64) -> ([u8; 10], usize) {
+    let mut buf = [0; 10];
+    let mut n = 0;
+    while v >= 0x80 {
+        buf[n] = v as u8 | 0x80;
+        v >>= 7;
+        n += 1;
+    }
+    buf[n] = v as u8;
+    (buf, n + 1)
+}
+
+/// Append `v` as a varint.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub(crate) fn put_varint(out: &mut Vec<u8>, v: u64) {
+    let (buf, n) = varint(v);
+    out.extend_from_slice(&buf[..n]);
+}
+
+/// Where encoders write: a growable `Vec<u8>` or a fixed `heapless::Vec<u8, N>`.
+pub trait ByteSink {
+    fn put(&mut self, bytes: &[u8]) -> Result<(), EncodeError>;
+
+    /// Write `v` as an unsigned LEB128 varint (1 to 10 bytes).
+    fn put_varint(&mut self, v: u64) -> Result<(), EncodeError> {
+        let (buf, n) = varint(v);
+        self.put(&buf[..n])
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl ByteSink for Vec<u8> {
+    fn put(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl<const N: usize> ByteSink for heapless::Vec<u8, N> {
+    fn put(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        self.extend_from_slice(bytes).map_err(|_| EncodeError)
+    }
+}
+
+/// Cursor over encoded bytes.
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    /// Bytes consumed so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Error pointing at the current position.
+    pub fn error(&self) -> DecodeError {
+        DecodeError { offset: self.pos }
+    }
+
+    /// The next `n` bytes.
+    pub fn take(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.bytes.len()).ok_or(self.error())?;
+        let out = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// An unsigned LEB128 varint, as written by `ByteSink::put_varint`.
+    pub fn varint(&mut self) -> Result<u64, DecodeError> {
+        let start = self.pos;
+        let mut v = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = *self.bytes.get(self.pos).ok_or(self.error())?;
+            self.pos += 1;
+            // The tenth byte may only carry the top bit of a u64.
+            if shift == 63 && b > 1 {
+                break;
+            }
+            v |= u64::from(b & 0x7f) << shift;
+            if b & 0x80 == 0 {
+                return Ok(v);
+            }
+        }
+        Err(DecodeError { offset: start })
+    }
+
+    pub fn u64_le(&mut self) -> Result<u64, DecodeError> {
+        let b = self.take(8)?;
+        Ok(u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+    }
+
+    /// Fails unless every byte was consumed.
+    pub fn finish(self) -> Result<(), DecodeError> {
+        if self.pos == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+}
+
+/// Binary encoding of event payloads, used by `Scheduler::snapshot` and `restore`.
+/// `decode` must read back exactly what `encode` wrote; the format is self-delimiting,
+/// so no length prefix is stored.
+pub trait PayloadCodec: Sized {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) -> Result<(), EncodeError>;
+    fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError>;
+}
+
+impl PayloadCodec for () {
+    fn encode<W: ByteSink + ?Sized>(&self, _out: &mut W) -> Result<(), EncodeError> {
+        Ok(())
+    }
+
+    fn decode(_r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        Ok(())
+    }
+}
+
+impl PayloadCodec for bool {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) -> Result<(), EncodeError> {
+        out.put(&[u8::from(*self)])
+    }
+
+    fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        match r.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeError { offset: r.position() - 1 }),
+        }
+    }
+}
+
+macro_rules! unsigned_codec {
+    ($($t:ty),*) => {$(
+        impl PayloadCodec for $t {
+            fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) -> Result<(), EncodeError> {
+                out.put_varint(*self as u64)
+            }
+
+            fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+                let start = r.position();
+                <$t>::try_from(r.varint()?).map_err(|_| DecodeError { offset: start })
+            }
+        }
+    )*};
+}
+
+// Signed values are zigzag-encoded so small magnitudes stay short.
+macro_rules! signed_codec {
+    ($($t:ty),*) => {$(
+        impl PayloadCodec for $t {
+            fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) -> Result<(), EncodeError> {
+                let v = *self as i64;
+                out.put_varint(((v << 1) ^ (v >> 63)) as u64)
+            }
+
+            fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+                let start = r.position();
+                let z = r.varint()?;
+                let v = (z >> 1) as i64 ^ -((z & 1) as i64);
+                <$t>::try_from(v).map_err(|_| DecodeError { offset: start })
+            }
+        }
+    )*};
+}
+
+unsigned_codec!(u8, u16, u32, u64, usize);
+signed_codec!(i8, i16, i32, i64, isize);
@@ -0,0 +1,64 @@
+// kairos-scheduler/src/error.rs
+use core::fmt;
+
+use crate::scheduler::EventId;
+
+/// Why a scheduler operation failed.
+/// Variants raised while scheduling hand the rejected payload back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchedulerError<T> {
+    /// The storage backend has no room for another pending event.
+    CapacityFull(T),
+    /// The id was never issued by this scheduler.
+    UnknownId(EventId),
+    /// The id was issued but is no longer pending: the event fired or was cancelled.
+    NotPending(EventId),
+    /// The deadline is behind `now()` and the past-deadline policy is `Reject`.
+    DeadlineInPast(T),
+    /// The deadline does not fit in a `VInstant`.
+    ClockOverflow(T),
+    /// The recurrence rule has a zero period, zero repetitions, or ends before its
+    /// first occurrence.
+    InvalidRecurrence(T),
+}
+
+impl<T> SchedulerError<T> {
+    /// The payload handed back, if this error carries one.
+    pub fn into_payload(self) -> Option<T> {
+        match self {
+            SchedulerError::CapacityFull(p)
+            | SchedulerError::DeadlineInPast(p)
+            | SchedulerError::ClockOverflow(p)
+            | SchedulerError::InvalidRecurrence(p) => Some(p),
+            SchedulerError::UnknownId(_) | SchedulerError::NotPending(_) => None,
+        }
+    }
+
+    /// Convert the payload handed back, keeping the variant.
+    pub fn map_payload<U>(self, f: impl FnOnce(T) -> U) -> SchedulerError<U> {
+        match self {
+            SchedulerError::CapacityFull(p) => SchedulerError::CapacityFull(f(p)),
+            SchedulerError::UnknownId(id) => SchedulerError::UnknownId(id),
+            SchedulerError::NotPending(id) => SchedulerError::NotPending(id),
+            SchedulerError::DeadlineInPast(p) => SchedulerError::DeadlineInPast(f(p)),
+            SchedulerError::ClockOverflow(p) => SchedulerError::ClockOverflow(f(p)),
+            SchedulerError::InvalidRecurrence(p) => SchedulerError::InvalidRecurrence(f(p)),
+        }
+    }
+}
+
+impl<T> fmt::Display for SchedulerError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchedulerError::CapacityFull(_) => f.write_str("scheduler storage is full"),
+            SchedulerError::UnknownId(id) => write!(f, "unknown event id {}", id.0),
+            SchedulerError::NotPending(id) => write!(f, "event {} is no longer pending", id.0),
+            SchedulerError::DeadlineInPast(_) => f.write_str("deadline is in the past"),
+            SchedulerError::ClockOverflow(_) => f.write_str("deadline overflows virtual time"),
+            SchedulerError::InvalidRecurrence(_) => f.write_str("invalid recurrence rule"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for SchedulerError<T> {}
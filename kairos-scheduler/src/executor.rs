@@ -0,0 +1,310 @@
+// kairos-scheduler/src/executor.rs
+
+use core::cell::RefCell;
+use core::future::{poll_fn, Future};
+use core::pin::Pin;
+use core::task::{Context as TaskContext, Poll, Waker};
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::task::Wake;
+
+use kairos_core::{Clock, VDuration, VInstant};
+
+use crate::{EventId, Scheduler};
+
+/// Handle to the virtual-time timers of an `Executor`.
+/// Cheap to clone; move a copy into each task that needs to sleep.
+pub struct Timer<C: Clock> {
+    sched: Rc<RefCell<Scheduler<Waker, C>>>,
+}
+
+impl<C: Clock> Clone for Timer<C> {
+    fn clone(&self) -> Self {
+        Self { sched: Rc::clone(&self.sched) }
+    }
+}
+
+impl<C: Clock> Timer<C> {
+    /// Current virtual time.
+    pub fn now(&self) -> VInstant {
+        self.sched.borrow().now()
+    }
+
+    /// Future that completes once `dur` of virtual time has passed.
+    pub fn sleep(&self, dur: VDuration) -> Sleep<C> {
+        self.sleep_until(VInstant(self.now().0.saturating_add(dur.0)))
+    }
+
+    /// Future that completes once virtual time reaches `deadline`.
+    pub fn sleep_until(&self, deadline: VInstant) -> Sleep<C> {
+        Sleep { timer: self.clone(), deadline, id: None }
+    }
+
+    /// Ticks every `period`, starting one period from now (fixed-rate: a late tick
+    /// does not shift the ones after it).
+    pub fn interval(&self, period: VDuration) -> Interval<C> {
+        Interval { sleep: self.sleep(period), period }
+    }
+}
+
+/// Future returned by `Timer::sleep`; completes at its deadline in virtual time.
+/// Dropping it cancels the underlying timer.
+pub struct Sleep<C: Clock> {
+    timer: Timer<C>,
+    deadline: VInstant,
+    id: Option<EventId>,
+}
+
+impl<C: Clock> Sleep<C> {
+    /// Instant at which this future completes.
+    pub fn deadline(&self) -> VInstant {
+        self.deadline
+    }
+
+    /// Move the deadline, whether or not the sleep already completed.
+    pub fn reset(&mut self, deadline: VInstant) {
+        self.disarm();
+        self.deadline = deadline;
+    }
+
+    fn disarm(&mut self) {
+        if let Some(id) = self.id.take() {
+            let _ = self.timer.sched.borrow_mut().cancel(id);
+        }
+    }
+}
+
+impl<C: Clock> Future for Sleep<C> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<()> {
+        let this = &mut *self;
+        let mut sched = this.timer.sched.borrow_mut();
+        if sched.now() >= this.deadline {
+            if let Some(id) = this.id.take() {
+                let _ = sched.cancel(id);
+            }
+            return Poll::Ready(());
+        }
+        let waker = cx.waker().clone();
+        // Refresh the waker of a pending timer, or arm a new one.
+        let waker = match this.id {
+            Some(id) => match sched.modify_event(id, None, Some(waker)) {
+                Ok(()) => return Poll::Pending,
+                Err(err) => err.into_payload().flatten().unwrap_or_else(|| cx.waker().clone()),
+            },
+            None => waker,
+        };
+        // The deadline is ahead of `now()`, so only an unbounded backend could refuse it.
+        this.id = sched.schedule_at(this.deadline, waker).ok();
+        Poll::Pending
+    }
+}
+
+impl<C: Clock> Drop for Sleep<C> {
+    fn drop(&mut self) {
+        self.disarm();
+    }
+}
+
+/// Periodic timer returned by `Timer::interval`.
+/// Also a `futures_core::Stream` of tick instants.
+pub struct Interval<C: Clock> {
+    sleep: Sleep<C>,
+    period: VDuration,
+}
+
+impl<C: Clock> Interval<C> {
+    /// Wait for the next tick; returns its scheduled instant.
+    pub async fn tick(&mut self) -> VInstant {
+        poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    /// Poll for the next tick, registering the task's waker if it is not due yet.
+    pub fn poll_tick(&mut self, cx: &mut TaskContext<'_>) -> Poll<VInstant> {
+        match Pin::new(&mut self.sleep).poll(cx) {
+            Poll::Ready(()) => {
+                let at = self.sleep.deadline();
+                self.sleep.reset(VInstant(at.0.saturating_add(self.period.0)));
+                Poll::Ready(at)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    /// Tick period.
+    pub fn period(&self) -> VDuration {
+        self.period
+    }
+}
+
+impl<C: Clock> futures_core::Stream for Interval<C> {
+    type Item = VInstant;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Option<VInstant>> {
+        self.poll_tick(cx).map(Some)
+    }
+}
+
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Ids of tasks woken since they were last polled.
+type ReadyQueue = Arc<Mutex<VecDeque<usize>>>;
+
+/// Id reserved for the future driven by `block_on`.
+const MAIN: usize = usize::MAX;
+
+struct TaskWaker {
+    id: usize,
+    ready: ReadyQueue,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let mut ready = self.ready.lock().unwrap_or_else(|e| e.into_inner());
+        if !ready.contains(&self.id) {
+            ready.push_back(self.id);
+        }
+    }
+}
+
+/// Single-threaded executor running async code under virtual time.
+///
+/// Tasks run until every one of them is waiting; the clock then jumps straight to the
+/// next timer deadline, whose sleepers are woken in scheduler order. Runs are therefore
+/// deterministic and take no wall-clock time with a `ManualClock`.
+pub struct Executor<C: Clock> {
+    timer: Timer<C>,
+    tasks: Vec<Option<Task>>,
+    ready: ReadyQueue,
+}
+
+impl<C: Clock> Executor<C> {
+    pub fn new(clock: C) -> Self {
+        Self {
+            timer: Timer { sched: Rc::new(RefCell::new(Scheduler::new(clock))) },
+            tasks: Vec::new(),
+            ready: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Handle for creating sleeps and intervals on this executor.
+    pub fn timer(&self) -> Timer<C> {
+        self.timer.clone()
+    }
+
+    /// Current virtual time.
+    pub fn now(&self) -> VInstant {
+        self.timer.now()
+    }
+
+    /// Add a background task; it first runs on the next `run` or `block_on`.
+    pub fn spawn<F: Future<Output = ()> + 'static>(&mut self, fut: F) {
+        let id = match self.tasks.iter().position(Option::is_none) {
+            Some(id) => id,
+            None => {
+                self.tasks.push(None);
+                self.tasks.len() - 1
+            }
+        };
+        self.tasks[id] = Some(Box::pin(fut));
+        self.wake(id);
+    }
+
+    /// Number of spawned tasks that have not finished.
+    pub fn pending_tasks(&self) -> usize {
+        self.tasks.iter().filter(|t| t.is_some()).count()
+    }
+
+    /// Run spawned tasks until all have finished or are waiting on something other
+    /// than a timer.
+    pub fn run(&mut self) {
+        while self.poll_ready(None) || self.fire_next_timers() {}
+    }
+
+    /// Like `run`, but only fires timers due by `deadline`, then moves the clock there.
+    /// Use it to observe tasks that never finish, such as interval loops.
+    pub fn run_until(&mut self, deadline: VInstant) {
+        loop {
+            self.poll_ready(None);
+            if self.timer.sched.borrow().peek_next().is_none_or(|at| at > deadline) {
+                break;
+            }
+            self.fire_next_timers();
+        }
+        self.timer.sched.borrow_mut().run_until(deadline, |_, waker| waker.wake());
+    }
+
+    /// Drive `fut` (and the spawned tasks) to completion.
+    /// Returns `None` if it can never complete: nothing is runnable and no timer is pending.
+    pub fn block_on<F: Future>(&mut self, fut: F) -> Option<F::Output> {
+        let mut fut = core::pin::pin!(fut);
+        let waker = self.waker(MAIN);
+        self.wake(MAIN);
+        loop {
+            let mut main_woken = false;
+            self.poll_ready(Some(&mut main_woken));
+            if main_woken {
+                if let Poll::Ready(out) = fut.as_mut().poll(&mut TaskContext::from_waker(&waker)) {
+                    return Some(out);
+                }
+                continue;
+            }
+            if !self.fire_next_timers() {
+                return None;
+            }
+        }
+    }
+
+    /// Poll every woken task; reports (and skips) a woken main future through `main`.
+    /// Returns whether anything was polled.
+    fn poll_ready(&mut self, mut main: Option<&mut bool>) -> bool {
+        let mut progressed = false;
+        while let Some(id) = self.next_ready() {
+            if id == MAIN {
+                if let Some(main) = main.as_deref_mut() {
+                    *main = true;
+                    return true;
+                }
+                continue;
+            }
+            let waker = self.waker(id);
+            let Some(task) = self.tasks.get_mut(id).and_then(Option::as_mut) else {
+                continue;
+            };
+            progressed = true;
+            if task.as_mut().poll(&mut TaskContext::from_waker(&waker)).is_ready() {
+                self.tasks[id] = None;
+            }
+        }
+        progressed
+    }
+
+    /// Advance the clock to the next deadline and wake everything due then.
+    /// Returns `false` if no timer is pending.
+    fn fire_next_timers(&mut self) -> bool {
+        let mut sched = self.timer.sched.borrow_mut();
+        let Some(at) = sched.peek_next() else {
+            return false;
+        };
+        sched.run_until(at, |_, waker| waker.wake());
+        true
+    }
+
+    fn next_ready(&self) -> Option<usize> {
+        self.ready.lock().unwrap_or_else(|e| e.into_inner()).pop_front()
+    }
+
+    fn wake(&self, id: usize) {
+        self.waker(id).wake();
+    }
+
+    fn waker(&self, id: usize) -> Waker {
+        Waker::from(Arc::new(TaskWaker { id, ready: Arc::clone(&self.ready) }))
+    }
+}
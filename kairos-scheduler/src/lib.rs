@@ -1,4 +1,38 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod codec;
+#[cfg(any(feature = "std", feature = "alloc"))]
+mod digest;
+mod error;
+#[cfg(feature = "async")]
+pub mod executor;
 mod scheduler;
+pub mod snapshot;
+pub mod storage;
+#[cfg(all(feature = "sim", any(feature = "std", feature = "alloc")))]
+pub mod sim;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod trace;
+
 pub use scheduler::Scheduler;
 pub use scheduler::Event;
 pub use scheduler::EventId;
+pub use scheduler::Recurrence;
+pub use scheduler::RepeatMode;
+pub use scheduler::PastDeadlinePolicy;
+pub use scheduler::Priority;
+pub use scheduler::Context;
+pub use scheduler::Slot;
+pub use error::SchedulerError;
+pub use storage::{SnapshotStorage, Storage};
+#[cfg(feature = "async")]
+pub use executor::Executor;
+#[cfg(all(feature = "sim", any(feature = "std", feature = "alloc")))]
+pub use sim::Sim;
+pub use codec::{ByteSink, DecodeError, EncodeError, PayloadCodec, Reader};
+pub use snapshot::RestoreError;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use trace::{Divergence, Trace, TraceEntry};
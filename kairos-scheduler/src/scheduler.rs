@@ -1,272 +1,561 @@
-#![cfg_attr(not(feature = "std"), no_std)]
-
-#[cfg(feature = "std")]
-use {
-    priority_queue::PriorityQueue,
-    std::collections::HashMap,
-    std::cmp::Reverse,
-};
-#[cfg(feature = "alloc")]
-extern crate alloc;
-#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
-use heapless::{BinaryHeap as HeaplessBinaryHeap, FnvIndexSet as HeaplessHashSet, binary_heap::Max};
-
 use kairos_core::{Clock, VInstant, VDuration};
 use core::cmp::Ordering;
+use core::marker::PhantomData;
+
+use crate::error::SchedulerError;
+use crate::storage::Storage;
+#[cfg(any(feature = "std", feature = "alloc"))]
+use crate::storage::PqStorage;
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+use crate::storage::HeaplessStorage;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct EventId(pub u64);
 
+/// Serialized as its bare number in every format.
+#[cfg(feature = "serde")]
+impl serde::Serialize for EventId {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for EventId {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        u64::deserialize(d).map(EventId)
+    }
+}
+
 /// Represents a scheduled event.
 /// `at` is the virtual time when the event should fire.
+/// `priority` orders events due at the same `at`; `seq` is the insertion sequence,
+/// breaking any remaining tie (FIFO).
 /// `payload` is the associated data to be passed to the event handler.
 #[derive(Debug)]
 pub struct Event<T> {
     pub id: EventId,
     pub at: VInstant,
+    pub priority: Priority,
+    pub seq: u64,
     pub payload: T,
 }
 
+/// Priority of an event among those due at the same instant: **lower values fire first**.
+/// The named classes leave room for finer-grained values in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Priority(pub u8);
+
+impl Priority {
+    pub const HIGHEST: Priority = Priority(0);
+    pub const HIGH: Priority = Priority(64);
+    pub const NORMAL: Priority = Priority(128);
+    pub const LOW: Priority = Priority(192);
+    pub const LOWEST: Priority = Priority(255);
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Priority::NORMAL
+    }
+}
+
+/// Firing order of pending events: earliest deadline first, then priority, then insertion order.
+/// Shared by every backend so ties resolve identically everywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Key {
+    pub(crate) at: VInstant,
+    pub(crate) priority: Priority,
+    pub(crate) seq: u64,
+}
+
+impl<T> Event<T> {
+    #[inline(always)]
+    pub(crate) fn key(&self) -> Key {
+        Key { at: self.at, priority: self.priority, seq: self.seq }
+    }
+
+    /// Borrow the payload, copying the rest; handy for `SnapshotStorage::for_each`.
+    #[inline(always)]
+    pub fn as_ref(&self) -> Event<&T> {
+        Event { id: self.id, at: self.at, priority: self.priority, seq: self.seq, payload: &self.payload }
+    }
+
+    #[inline(always)]
+    pub(crate) fn from_key(id: EventId, key: Key, payload: T) -> Self {
+        Event { id, at: key.at, priority: key.priority, seq: key.seq, payload }
+    }
+}
+
 // Priority queue ordering: earliest event = highest priority.
 impl<T> PartialEq for Event<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.at == other.at
+        self.key() == other.key()
     }
 }
 impl<T> Eq for Event<T> {}
 impl<T> PartialOrd for Event<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        // Reverse order so the BinaryHeap pops earliest event first.
-        Some(other.at.cmp(&self.at))
+        Some(self.cmp(other))
     }
 }
 impl<T> Ord for Event<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.at.cmp(&self.at)
+        // Reverse order so the BinaryHeap pops earliest event first.
+        other.key().cmp(&self.key())
     }
 }
 
-/// Simple time-driven scheduler that runs tasks at scheduled virtual times.
-/// Uses a `Clock` to track and advance time, allowing simulation or real-time modes.
-#[cfg(feature = "std")]
-pub struct Scheduler<T, C: Clock> {
-    clock: C,
-    pq: PriorityQueue<EventId, Reverse<VInstant>>,
-    events: HashMap<EventId, T>,
-    next_id: u64,
+/// How the next occurrence of a recurring event is computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RepeatMode {
+    /// Next deadline = previous *deadline* + period (catches up if firing ran late).
+    #[default]
+    FixedRate,
+    /// Next deadline = instant the previous occurrence actually fired + period.
+    FixedDelay,
 }
 
-#[cfg(feature = "std")]
-impl<T, C: Clock> Scheduler<T, C> {
-    /// Creates a new scheduler using the given clock.
-    pub fn new(clock: C) -> Self {
-        Self {
-            clock,
-            pq: PriorityQueue::new(),
-            events: HashMap::new(),
-            next_id: 0,
+/// Describes how a recurring event repeats.
+/// `period` must be non-zero; `max_repetitions` counts every firing, including the first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Recurrence {
+    pub period: VDuration,
+    pub mode: RepeatMode,
+    pub max_repetitions: Option<u32>,
+    pub until: Option<VInstant>,
+}
+
+impl Recurrence {
+    /// Repeat forever every `period`, fixed-rate.
+    pub const fn every(period: VDuration) -> Self {
+        Self { period, mode: RepeatMode::FixedRate, max_repetitions: None, until: None }
+    }
+
+    /// Select fixed-rate or fixed-delay semantics.
+    pub const fn with_mode(mut self, mode: RepeatMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Stop after the event has fired `n` times.
+    pub const fn with_max_repetitions(mut self, n: u32) -> Self {
+        self.max_repetitions = Some(n);
+        self
+    }
+
+    /// Never schedule an occurrence later than `end`.
+    pub const fn with_end(mut self, end: VInstant) -> Self {
+        self.until = Some(end);
+        self
+    }
+
+    /// Whether a first occurrence at `at` is valid for this rule.
+    fn admits(&self, at: VInstant) -> bool {
+        self.period.0 > 0
+            && self.max_repetitions != Some(0)
+            && self.until.is_none_or(|end| at <= end)
+    }
+
+    /// Deadline of the occurrence following one due at `at` that fired at `now`,
+    /// or `None` once the rule is exhausted.
+    fn next_after(&self, at: VInstant, now: VInstant, fired: u32) -> Option<VInstant> {
+        if self.max_repetitions.is_some_and(|max| fired >= max) {
+            return None;
+        }
+        let base = match self.mode {
+            RepeatMode::FixedRate => at,
+            RepeatMode::FixedDelay => now,
+        };
+        let next = VInstant(base.0.checked_add(self.period.0)?);
+        match self.until {
+            Some(end) if next > end => None,
+            _ => Some(next),
+        }
+    }
+}
+
+/// What `schedule_at` does with a deadline that is already behind `clock.now()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PastDeadlinePolicy {
+    /// Keep the requested deadline: the event is due at once and, carrying its earlier
+    /// timestamp, runs ahead of events due at `now`.
+    #[default]
+    FireImmediately,
+    /// Refuse the event; the payload is handed back.
+    Reject,
+    /// Move the deadline up to `now`, queueing behind events already due then.
+    Clamp,
+}
+
+impl PastDeadlinePolicy {
+    /// Effective deadline for a request at `at` when the clock reads `now`.
+    fn resolve(self, at: VInstant, now: VInstant) -> Option<VInstant> {
+        if at >= now {
+            return Some(at);
+        }
+        match self {
+            PastDeadlinePolicy::FireImmediately => Some(at),
+            PastDeadlinePolicy::Reject => None,
+            PastDeadlinePolicy::Clamp => Some(now),
         }
     }
+}
 
-    /// Schedule a new event to occur after a given duration.
-    pub fn schedule_in(&mut self, in_dur: VDuration, payload: T) -> Result<EventId, T> {
-        let at = self.clock.now() + in_dur;
-        let id = EventId(self.next_id);
-        self.next_id += 1;
-        self.pq.push(id, Reverse(at));
-        self.events.insert(id, payload);
-        Ok(id)
+/// Move `clock` forward to `at`; never moves it backwards.
+#[inline(always)]
+pub(crate) fn advance_to<C: Clock>(clock: &mut C, at: VInstant) {
+    let now = clock.now();
+    if at > now {
+        clock.advance(at - now);
     }
+}
 
-    /// Cancel a scheduled event.
-    pub fn cancel(&mut self, id: EventId) {
-        self.pq.remove(&id);
-        self.events.remove(&id);
+/// Stored payload plus the bookkeeping needed to re-arm recurring events.
+/// Opaque to storage backends, which keep `Event<Slot<T>>` values as-is.
+pub struct Slot<T> {
+    payload: T,
+    repeat: Option<Repeat<T>>,
+}
+
+struct Repeat<T> {
+    rule: Recurrence,
+    fired: u32,
+    // Captured at scheduling time so only recurring events require `T: Clone`.
+    clone: fn(&T) -> T,
+}
+
+impl<T> Slot<T> {
+    fn once(payload: T) -> Self {
+        Self { payload, repeat: None }
     }
 
-    /// Modify a scheduled event.
-    pub fn modify_event(&mut self, id: EventId, new_at: Option<VInstant>, new_payload: Option<T>) -> bool {
-        if let Some(mut priority) = self.pq.get_priority(&id).cloned() {
-            let mut modified = false;
-            if let Some(at) = new_at {
-                priority = Reverse(at);
-                modified = true;
-            }
+    fn recurring(payload: T, rule: Recurrence) -> Self
+    where
+        T: Clone,
+    {
+        Self { payload, repeat: Some(Repeat { rule, fired: 0, clone: T::clone }) }
+    }
 
-            if let Some(payload) = new_payload {
-                if let Some(event_payload) = self.events.get_mut(&id) {
-                    *event_payload = payload;
-                }
-            }
+    /// Rebuild a slot saved by a snapshot: the payload and, for a recurring event,
+    /// its rule and how many times it has fired.
+    pub(crate) fn restored(payload: T, repeat: Option<(Recurrence, u32)>) -> Self
+    where
+        T: Clone,
+    {
+        let repeat = repeat.map(|(rule, fired)| Repeat { rule, fired, clone: T::clone });
+        Self { payload, repeat }
+    }
+
+    pub(crate) fn payload(&self) -> &T {
+        &self.payload
+    }
 
-            if modified {
-                self.pq.change_priority(&id, priority);
+    pub(crate) fn recurrence(&self) -> Option<(Recurrence, u32)> {
+        self.repeat.as_ref().map(|r| (r.rule, r.fired))
+    }
+
+    /// Consume one firing of the event due at `at` (fired at `now`).
+    /// Returns the payload for the handler and, if the event recurs, the slot to re-arm
+    /// together with its next deadline.
+    fn fire(self, at: VInstant, now: VInstant) -> (T, Option<(VInstant, Self)>) {
+        let Some(mut repeat) = self.repeat else {
+            return (self.payload, None);
+        };
+        repeat.fired = repeat.fired.saturating_add(1);
+        match repeat.rule.next_after(at, now, repeat.fired) {
+            Some(next) => {
+                let out = (repeat.clone)(&self.payload);
+                (out, Some((next, Self { payload: self.payload, repeat: Some(repeat) })))
             }
-            true
-        } else {
-            false
+            None => (self.payload, None),
         }
     }
+}
 
-    /// Mutable reference to the underlying clock (for manual advancement).
-    pub fn clock_mut(&mut self) -> &mut C {
-        &mut self.clock
+/// Handle given to `run_until_with` handlers while an event fires.
+/// Exposes the scheduling API so cascading events join the run in progress.
+pub struct Context<'a, S> {
+    sched: &'a mut S,
+    id: EventId,
+}
+
+impl<S> Context<'_, S> {
+    /// Id of the event being handled (the whole series, for recurring events).
+    pub fn event_id(&self) -> EventId {
+        self.id
     }
+}
 
-    /// Returns the current virtual time.
+impl<T, C: Clock, S: Storage<T>> Context<'_, Scheduler<T, C, S>> {
+    /// Current virtual time.
     pub fn now(&self) -> VInstant {
-        self.clock.now()
+        self.sched.now()
     }
 
-    /// Runs all events up to a target time.
-    /// Advances the clock as events are processed.
-    pub fn run_until<F: FnMut(VInstant, T)>(&mut self, target: VInstant, mut on_event: F) {
-        while let Some((_id, at)) = self.pq.peek() {
-            if at.0 > target {
-                break;
-            }
-            let (id, at) = self.pq.pop().unwrap();
-            let payload = self.events.remove(&id).unwrap();
+    /// Schedule a follow-up event after `in_dur`.
+    pub fn schedule_in(&mut self, in_dur: VDuration, payload: T) -> Result<EventId, SchedulerError<T>> {
+        self.sched.schedule_in(in_dur, payload)
+    }
 
-            let delta = at.0 - self.clock.now();
-            self.clock.advance(delta);
-            on_event(self.clock.now(), payload);
-        }
-        // Advance to the target time even if no events remain.
-        let remaining = target - self.clock.now();
-        self.clock.advance(remaining);
+    /// Schedule a follow-up event after `in_dur` with an explicit priority.
+    pub fn schedule_in_with_priority(&mut self, in_dur: VDuration, priority: Priority, payload: T) -> Result<EventId, SchedulerError<T>> {
+        self.sched.schedule_in_with_priority(in_dur, priority, payload)
+    }
+
+    /// Schedule a follow-up event at an absolute virtual time.
+    pub fn schedule_at(&mut self, at: VInstant, payload: T) -> Result<EventId, SchedulerError<T>> {
+        self.sched.schedule_at(at, payload)
     }
+
+    /// Schedule a follow-up event at an absolute virtual time with an explicit priority.
+    pub fn schedule_at_with_priority(&mut self, at: VInstant, priority: Priority, payload: T) -> Result<EventId, SchedulerError<T>> {
+        self.sched.schedule_at_with_priority(at, priority, payload)
+    }
+
+    /// Cancel a pending event (including the current one's next occurrence).
+    /// Returns the payload if the event was pending.
+    pub fn cancel(&mut self, id: EventId) -> Result<T, SchedulerError<T>> {
+        self.sched.cancel(id)
+    }
+}
+
+/// Simple time-driven scheduler that runs tasks at scheduled virtual times.
+/// Uses a `Clock` to track and advance time, allowing simulation or real-time modes.
+/// Pending events live in a pluggable `Storage` backend; every backend fires events
+/// in the same order.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct Scheduler<T, C: Clock, S = PqStorage<T>> {
+    pub(crate) clock: C,
+    pub(crate) queue: S,
+    pub(crate) next_id: u64,
+    pub(crate) next_seq: u64,
+    past_policy: PastDeadlinePolicy,
+    _payload: PhantomData<fn() -> T>,
 }
 
-#[cfg(all(not(feature = "std"), feature = "alloc"))]
-use {
-    alloc::collections::BTreeMap,
-    core::cmp::Reverse,
-    priority_queue::PriorityQueue,
-    ahash,
-};
-
-#[cfg(all(not(feature = "std"), feature = "alloc"))]
-pub struct Scheduler<T, C: Clock> {
-    clock: C,
-    pq: PriorityQueue<EventId, Reverse<VInstant>, ahash::RandomState>,
-    events: BTreeMap<EventId, T>,
-    next_id: u64,
+/// Simple time-driven scheduler that runs tasks at scheduled virtual times.
+/// Uses a `Clock` to track and advance time, allowing simulation or real-time modes.
+/// Without an allocator, pick a fixed-capacity backend such as `HeaplessStorage<T, N>`.
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+pub struct Scheduler<T, C: Clock, S> {
+    pub(crate) clock: C,
+    pub(crate) queue: S,
+    pub(crate) next_id: u64,
+    pub(crate) next_seq: u64,
+    past_policy: PastDeadlinePolicy,
+    _payload: PhantomData<fn() -> T>,
 }
 
-#[cfg(all(not(feature = "std"), feature = "alloc"))]
+#[cfg(any(feature = "std", feature = "alloc"))]
 impl<T, C: Clock> Scheduler<T, C> {
-    /// Creates a new scheduler using the given clock.
+    /// Creates a new scheduler using the given clock and the default storage.
     pub fn new(clock: C) -> Self {
+        Self::with_storage(clock, PqStorage::new())
+    }
+}
+
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+impl<T, C: Clock, const N: usize> Scheduler<T, C, HeaplessStorage<T, N>> {
+    /// Creates a new scheduler using the given clock, holding at most `N` pending events.
+    pub fn new(clock: C) -> Self {
+        Self::with_storage(clock, HeaplessStorage::new())
+    }
+}
+
+impl<T, C: Clock, S: Storage<T>> Scheduler<T, C, S> {
+    /// Creates a new scheduler using the given clock and storage backend.
+    pub fn with_storage(clock: C, storage: S) -> Self {
         Self {
             clock,
-            pq: PriorityQueue::with_hasher(ahash::RandomState::new()),
-            events: BTreeMap::new(),
+            queue: storage,
             next_id: 0,
+            next_seq: 0,
+            past_policy: PastDeadlinePolicy::default(),
+            _payload: PhantomData,
         }
     }
 
+    /// Choose how `schedule_at` treats deadlines already in the past.
+    pub fn with_past_policy(mut self, policy: PastDeadlinePolicy) -> Self {
+        self.past_policy = policy;
+        self
+    }
+
+    /// Change the past-deadline policy of a running scheduler.
+    pub fn set_past_policy(&mut self, policy: PastDeadlinePolicy) {
+        self.past_policy = policy;
+    }
+
     /// Schedule a new event to occur after a given duration.
-    pub fn schedule_in(&mut self, in_dur: VDuration, payload: T) -> Result<EventId, T> {
-        let at = self.clock.now() + in_dur;
+    pub fn schedule_in(&mut self, in_dur: VDuration, payload: T) -> Result<EventId, SchedulerError<T>> {
+        self.schedule_in_with_priority(in_dur, Priority::NORMAL, payload)
+    }
+
+    /// Like `schedule_in`, with an explicit priority among events due at the same instant.
+    pub fn schedule_in_with_priority(&mut self, in_dur: VDuration, priority: Priority, payload: T) -> Result<EventId, SchedulerError<T>> {
+        let at = match self.deadline_in(in_dur) {
+            Some(at) => at,
+            None => return Err(SchedulerError::ClockOverflow(payload)),
+        };
+        self.insert(at, priority, Slot::once(payload))
+    }
+
+    /// Schedule a new event at an absolute virtual time.
+    /// Deadlines behind `now()` are handled according to the past-deadline policy.
+    pub fn schedule_at(&mut self, at: VInstant, payload: T) -> Result<EventId, SchedulerError<T>> {
+        self.schedule_at_with_priority(at, Priority::NORMAL, payload)
+    }
+
+    /// Like `schedule_at`, with an explicit priority among events due at the same instant.
+    pub fn schedule_at_with_priority(&mut self, at: VInstant, priority: Priority, payload: T) -> Result<EventId, SchedulerError<T>> {
+        match self.past_policy.resolve(at, self.clock.now()) {
+            Some(at) => self.insert(at, priority, Slot::once(payload)),
+            None => Err(SchedulerError::DeadlineInPast(payload)),
+        }
+    }
+
+    /// Schedule an event that fires every `period`, starting one period from now.
+    pub fn schedule_every(&mut self, period: VDuration, payload: T) -> Result<EventId, SchedulerError<T>>
+    where
+        T: Clone,
+    {
+        self.schedule_recurring(period, Recurrence::every(period), payload)
+    }
+
+    /// Schedule a recurring event whose first occurrence is `first_in` from now.
+    /// Every occurrence shares the returned `EventId`, so `cancel` stops the whole series.
+    /// Fails with `InvalidRecurrence` if the rule has a zero period, zero repetitions, or ends
+    /// before the first occurrence.
+    pub fn schedule_recurring(&mut self, first_in: VDuration, rule: Recurrence, payload: T) -> Result<EventId, SchedulerError<T>>
+    where
+        T: Clone,
+    {
+        self.schedule_recurring_with_priority(first_in, rule, Priority::NORMAL, payload)
+    }
+
+    /// Like `schedule_recurring`; every occurrence keeps `priority`.
+    pub fn schedule_recurring_with_priority(
+        &mut self,
+        first_in: VDuration,
+        rule: Recurrence,
+        priority: Priority,
+        payload: T,
+    ) -> Result<EventId, SchedulerError<T>>
+    where
+        T: Clone,
+    {
+        let at = match self.deadline_in(first_in) {
+            Some(at) => at,
+            None => return Err(SchedulerError::ClockOverflow(payload)),
+        };
+        if !rule.admits(at) {
+            return Err(SchedulerError::InvalidRecurrence(payload));
+        }
+        self.insert(at, priority, Slot::recurring(payload, rule))
+    }
+
+    fn insert(&mut self, at: VInstant, priority: Priority, slot: Slot<T>) -> Result<EventId, SchedulerError<T>> {
         let id = EventId(self.next_id);
+        let seq = self.take_seq();
+        self.queue
+            .push(Event { id, at, priority, seq, payload: slot })
+            .map_err(|ev| SchedulerError::CapacityFull(ev.payload.payload))?;
         self.next_id += 1;
-        self.pq.push(id, Reverse(at));
-        self.events.insert(id, payload);
         Ok(id)
     }
 
-    /// Cancel a scheduled event.
-    pub fn cancel(&mut self, id: EventId) {
-        self.pq.remove(&id);
-        self.events.remove(&id);
+    /// Deadline `in_dur` from now, or `None` if it overflows virtual time.
+    fn deadline_in(&self, in_dur: VDuration) -> Option<VInstant> {
+        self.clock.now().0.checked_add(in_dur.0).map(VInstant)
     }
 
-    /// Modify a scheduled event.
-    pub fn modify_event(&mut self, id: EventId, new_at: Option<VInstant>, new_payload: Option<T>) -> bool {
-        if let Some(mut priority) = self.pq.get_priority(&id).cloned() {
-            let mut modified = false;
-            if let Some(at) = new_at {
-                priority = Reverse(at);
-                modified = true;
-            }
-
-            if let Some(payload) = new_payload {
-                if let Some(event_payload) = self.events.get_mut(&id) {
-                    *event_payload = payload;
-                }
-            }
-
-            if modified {
-                self.pq.change_priority(&id, priority);
-            }
-            true
+    /// Error for an id that is not pending: never issued, or already fired or cancelled.
+    fn not_pending<E>(&self, id: EventId) -> SchedulerError<E> {
+        if id.0 >= self.next_id {
+            SchedulerError::UnknownId(id)
         } else {
-            false
+            SchedulerError::NotPending(id)
         }
     }
 
-    /// Mutable reference to the underlying clock (for manual advancement).
-    pub fn clock_mut(&mut self) -> &mut C {
-        &mut self.clock
+    /// Next insertion sequence number; every (re)insertion takes a fresh one.
+    fn take_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
     }
 
-    /// Returns the current virtual time.
-    pub fn now(&self) -> VInstant {
-        self.clock.now()
+    /// Put back an event taken out of the queue.
+    fn reinsert(&mut self, ev: Event<Slot<T>>) {
+        // Cannot fail: the event was just removed, so there is room for it.
+        let _ = self.queue.push(ev);
     }
 
-    /// Runs all events up to a target time.
-    /// Advances the clock as events are processed.
-    pub fn run_until<F: FnMut(VInstant, T)>(&mut self, target: VInstant, mut on_event: F) {
-        while let Some((_id, at)) = self.pq.peek() {
-            if at.0 > target {
-                break;
-            }
-            let (id, at) = self.pq.pop().unwrap();
-            let payload = self.events.remove(&id).unwrap();
+    /// Cancel a scheduled event, freeing its storage slot immediately.
+    /// Returns the payload of the pending event; fired or already-cancelled ids are
+    /// reported as `NotPending`.
+    pub fn cancel(&mut self, id: EventId) -> Result<T, SchedulerError<T>> {
+        match self.queue.remove(id) {
+            Some(ev) => Ok(ev.payload.payload),
+            None => Err(self.not_pending(id)),
+        }
+    }
 
-            let delta = at.0 - self.clock.now();
-            self.clock.advance(delta);
-            on_event(self.clock.now(), payload);
+    /// Modify a scheduled event.
+    /// A new deadline queues the event behind others already due at that instant.
+    /// On failure the event is left untouched and `new_payload` is handed back in the error
+    /// (`DeadlineInPast` when the past-deadline policy rejects `new_at`).
+    pub fn modify_event(
+        &mut self,
+        id: EventId,
+        new_at: Option<VInstant>,
+        new_payload: Option<T>,
+    ) -> Result<(), SchedulerError<Option<T>>> {
+        let new_at = match new_at {
+            Some(at) => match self.past_policy.resolve(at, self.clock.now()) {
+                Some(at) => Some(at),
+                None => return Err(SchedulerError::DeadlineInPast(new_payload)),
+            },
+            None => None,
+        };
+        let Some(mut ev) = self.queue.remove(id) else {
+            return Err(self.not_pending(id));
+        };
+        if let Some(at) = new_at {
+            ev.at = at;
+            ev.seq = self.take_seq();
         }
-        // Advance to the target time even if no events remain.
-        let remaining = target - self.clock.now();
-        self.clock.advance(remaining);
+        if let Some(payload) = new_payload {
+            ev.payload.payload = payload;
+        }
+        self.reinsert(ev);
+        Ok(())
     }
-}
 
-#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
-pub struct Scheduler<T, C: Clock, const N: usize> {
-    clock: C,
-    pq: HeaplessBinaryHeap<Event<T>, Max, N>,
-    next_id: u64,
-    cancelled_ids: HeaplessHashSet<EventId, N>,
-}
+    /// Change the priority of a pending event; its deadline and place among equals are kept.
+    pub fn set_priority(&mut self, id: EventId, priority: Priority) -> Result<(), SchedulerError<T>> {
+        let Some(mut ev) = self.queue.remove(id) else {
+            return Err(self.not_pending(id));
+        };
+        ev.priority = priority;
+        self.reinsert(ev);
+        Ok(())
+    }
 
-#[cfg(all(not(feature = "std"), not(feature = "alloc")))]
-impl<T: core::fmt::Debug, C: Clock, const N: usize> Scheduler<T, C, N> {
-    /// Creates a new scheduler using the given clock.
-    pub fn new(clock: C) -> Self {
-        Self {
-            clock,
-            pq: HeaplessBinaryHeap::new(),
-            next_id: 0,
-            cancelled_ids: HeaplessHashSet::new(),
-        }
+    /// Number of pending events (a recurring series counts once).
+    pub fn len(&self) -> usize {
+        self.queue.len()
     }
 
-    /// Schedule a new event to occur after a given duration.
-    pub fn schedule_in(&mut self, in_dur: VDuration, payload: T) -> Result<EventId, T> {
-        let at = self.clock.now() + in_dur;
-        let id = EventId(self.next_id);
-        self.next_id += 1;
-        self.pq.push(Event { id, at, payload }).map(|_| id).map_err(|e| e.payload)
+    /// Whether no events are pending.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
     }
 
-    /// Cancel a scheduled event.
-    pub fn cancel(&mut self, id: EventId) {
-        self.cancelled_ids.insert(id).unwrap();
+    /// Drop every pending event. The clock and id counter are left untouched.
+    pub fn clear(&mut self) {
+        self.queue.clear();
     }
 
     /// Mutable reference to the underlying clock (for manual advancement).
@@ -279,24 +568,72 @@ impl<T: core::fmt::Debug, C: Clock, const N: usize> Scheduler<T, C, N> {
         self.clock.now()
     }
 
+    /// Deadline of the next pending event, if any.
+    pub fn peek_next(&self) -> Option<VInstant> {
+        self.queue.peek()
+    }
+
+    /// Fire exactly one event — the earliest pending — advancing the clock to it.
+    /// Returns the fired event, or `None` if nothing is pending.
+    pub fn step(&mut self) -> Option<Event<T>> {
+        self.dispatch_next(VInstant(u64::MAX)).map(|(_, ev)| ev)
+    }
+
     /// Runs all events up to a target time.
     /// Advances the clock as events are processed.
     pub fn run_until<F: FnMut(VInstant, T)>(&mut self, target: VInstant, mut on_event: F) {
-        while let Some(ev) = self.pq.peek() {
-            if ev.at > target {
-                break;
-            }
-            let ev = self.pq.pop().unwrap();
-            if self.cancelled_ids.contains(&ev.id) {
-                self.cancelled_ids.remove(&ev.id);
-                continue;
-            }
-            let delta = ev.at - self.clock.now();
-            self.clock.advance(delta);
-            on_event(self.clock.now(), ev.payload);
+        self.run_until_with(target, |ctx, payload| on_event(ctx.now(), payload));
+    }
+
+    /// Like `run_until`, but the handler gets a `Context` to schedule or cancel events mid-run.
+    /// Follow-ups due at or before `target` fire within this same call, in order.
+    pub fn run_until_with<F>(&mut self, target: VInstant, mut on_event: F)
+    where
+        F: FnMut(&mut Context<'_, Self>, T),
+    {
+        while let Some((_, ev)) = self.dispatch_next(target) {
+            on_event(&mut Context { sched: self, id: ev.id }, ev.payload);
         }
         // Advance to the target time even if no events remain.
-        let remaining = target - self.clock.now();
-        self.clock.advance(remaining);
+        advance_to(&mut self.clock, target);
+    }
+
+    /// Runs all events due within `dur` from now, then advances the clock by `dur`.
+    pub fn run_for<F: FnMut(VInstant, T)>(&mut self, dur: VDuration, on_event: F) {
+        let target = self.clock.now() + dur;
+        self.run_until(target, on_event);
+    }
+
+    /// Runs events until none are pending, firing at most `max_events` of them
+    /// (a guard against recurring events that never end). The clock stops at the last event.
+    /// Returns how many events fired; check `peek_next()` to tell whether the cap was hit.
+    pub fn run_until_idle<F: FnMut(VInstant, T)>(&mut self, max_events: usize, mut on_event: F) -> usize {
+        let mut fired = 0;
+        while fired < max_events {
+            let Some((now, ev)) = self.dispatch_next(VInstant(u64::MAX)) else {
+                break;
+            };
+            on_event(now, ev.payload);
+            fired += 1;
+        }
+        fired
+    }
+
+    /// Pop the earliest event if it is due at or before `limit`, advance the clock to it
+    /// and re-arm it if it recurs. Returns the clock reading and the event to hand out.
+    pub(crate) fn dispatch_next(&mut self, limit: VInstant) -> Option<(VInstant, Event<T>)> {
+        if self.queue.peek()? > limit {
+            return None;
+        }
+        let ev = self.queue.pop()?;
+        let key = ev.key();
+        advance_to(&mut self.clock, key.at);
+        let now = self.clock.now();
+        let (payload, rearm) = ev.payload.fire(key.at, now);
+        if let Some((next, slot)) = rearm {
+            let seq = self.take_seq();
+            self.reinsert(Event { id: ev.id, at: next, priority: key.priority, seq, payload: slot });
+        }
+        Some((now, Event::from_key(ev.id, key, payload)))
     }
 }
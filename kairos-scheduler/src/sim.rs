@@ -0,0 +1,273 @@
+// kairos-scheduler/src/sim.rs
+
+use core::hash::{Hash, Hasher};
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use kairos_core::{Clock, KairosRng, ManualClock, VDuration, VInstant};
+
+use crate::digest::Fnv64;
+use crate::{EventId, Scheduler, SchedulerError};
+
+/// Identifies a process within a `Sim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct ProcessId(pub u32);
+
+impl ProcessId {
+    /// Sender of messages injected from outside the simulation with `Sim::post`.
+    pub const EXTERNAL: ProcessId = ProcessId(u32::MAX);
+}
+
+/// What a process is woken up for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input<M> {
+    /// First activation, right after `Sim::spawn`.
+    Start,
+    /// A message sent by `from`.
+    Message { from: ProcessId, msg: M },
+    /// A timer set with `SimContext::set_timer`.
+    Timer { tag: u64 },
+}
+
+impl<M> Input<M> {
+    fn kind(&self) -> u8 {
+        match self {
+            Input::Start => 0,
+            Input::Message { .. } => 1,
+            Input::Timer { .. } => 2,
+        }
+    }
+}
+
+/// A logical process: a state machine reacting to its inputs.
+/// Any `FnMut(&mut SimContext<'_, M>, Input<M>)` closure is a process.
+pub trait Process<M> {
+    fn handle(&mut self, ctx: &mut SimContext<'_, M>, input: Input<M>);
+}
+
+impl<M, F> Process<M> for F
+where
+    F: FnMut(&mut SimContext<'_, M>, Input<M>),
+{
+    fn handle(&mut self, ctx: &mut SimContext<'_, M>, input: Input<M>) {
+        self(ctx, input)
+    }
+}
+
+/// Message latency: uniform between `min` and `max` (inclusive), drawn from the
+/// sender's random stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Latency {
+    pub min: VDuration,
+    pub max: VDuration,
+}
+
+impl Latency {
+    pub const fn fixed(d: VDuration) -> Self {
+        Self { min: d, max: d }
+    }
+
+    pub const fn uniform(min: VDuration, max: VDuration) -> Self {
+        Self { min, max }
+    }
+
+    fn sample(&self, rng: &mut KairosRng) -> VDuration {
+        rng.uniform(self.min, self.max)
+    }
+}
+
+struct Delivery<M> {
+    to: ProcessId,
+    from: ProcessId,
+    input: Input<M>,
+}
+
+/// Latency settings: a default plus per-link overrides.
+struct Network {
+    default: Latency,
+    links: BTreeMap<(ProcessId, ProcessId), Latency>,
+}
+
+impl Network {
+    fn latency(&self, from: ProcessId, to: ProcessId) -> Latency {
+        self.links.get(&(from, to)).copied().unwrap_or(self.default)
+    }
+}
+
+/// Handle given to a process while it handles one input.
+pub struct SimContext<'a, M> {
+    me: ProcessId,
+    sched: &'a mut Scheduler<Delivery<M>, ManualClock>,
+    rng: &'a mut KairosRng,
+    net: &'a Network,
+    digest: &'a mut Fnv64,
+}
+
+impl<M> SimContext<'_, M> {
+    /// The process being run.
+    pub fn me(&self) -> ProcessId {
+        self.me
+    }
+
+    /// Current virtual time.
+    pub fn now(&self) -> VInstant {
+        self.sched.now()
+    }
+
+    /// Send `msg` to `to`; it arrives after the link's latency.
+    /// Messages on the same link with equal arrival times are delivered in send order.
+    pub fn send(&mut self, to: ProcessId, msg: M) {
+        let delay = self.net.latency(self.me, to).sample(self.rng);
+        let delivery = Delivery { to, from: self.me, input: Input::Message { from: self.me, msg } };
+        // Deadlines past the end of virtual time are dropped like lost packets.
+        let _ = self.sched.schedule_in(delay, delivery);
+    }
+
+    /// Wake this process with `Input::Timer { tag }` after `after`.
+    pub fn set_timer(&mut self, after: VDuration, tag: u64) -> Result<EventId, SchedulerError<u64>> {
+        let delivery = Delivery { to: self.me, from: self.me, input: Input::Timer { tag } };
+        self.sched.schedule_in(after, delivery).map_err(|e| e.map_payload(|_| tag))
+    }
+
+    /// Cancel a pending timer; returns its tag.
+    pub fn cancel_timer(&mut self, id: EventId) -> Option<u64> {
+        match self.sched.cancel(id).ok()?.input {
+            Input::Timer { tag } => Some(tag),
+            _ => None,
+        }
+    }
+
+    /// This process's random stream, forked from the run's seed by process id;
+    /// draw from it for any randomness the process needs.
+    pub fn rng(&mut self) -> &mut KairosRng {
+        self.rng
+    }
+
+    /// Fold application state into the run digest.
+    pub fn record<H: Hash + ?Sized>(&mut self, value: &H) {
+        value.hash(self.digest);
+    }
+}
+
+/// Deterministic simulation runtime.
+///
+/// Processes exchange messages and set timers; every delivery is an event of a
+/// `Scheduler` driven by a `ManualClock`, and all randomness (latency jitter included)
+/// comes from one seed, forked into a stream per process. The same seed and the same
+/// processes replay the same run, which `digest()` summarises for regression tests.
+pub struct Sim<M> {
+    sched: Scheduler<Delivery<M>, ManualClock>,
+    procs: Vec<(Box<dyn Process<M>>, KairosRng)>,
+    rng: KairosRng,
+    net: Network,
+    digest: Fnv64,
+}
+
+impl<M> Sim<M> {
+    /// New simulation at virtual time zero; messages arrive instantly by default.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            sched: Scheduler::new(ManualClock::new()),
+            procs: Vec::new(),
+            rng: KairosRng::seed_from_u64(seed),
+            net: Network { default: Latency::fixed(VDuration(0)), links: BTreeMap::new() },
+            digest: Fnv64::new(),
+        }
+    }
+
+    /// Latency of every link without an override.
+    pub fn with_latency(mut self, latency: Latency) -> Self {
+        self.net.default = latency;
+        self
+    }
+
+    /// Latency of messages from `from` to `to` (one direction).
+    pub fn set_link_latency(&mut self, from: ProcessId, to: ProcessId, latency: Latency) {
+        self.net.links.insert((from, to), latency);
+    }
+
+    /// Add a process; it receives `Input::Start` at the current instant.
+    pub fn spawn<P: Process<M> + 'static>(&mut self, process: P) -> ProcessId {
+        let id = ProcessId(self.procs.len() as u32);
+        self.procs.push((Box::new(process), self.rng.fork(u64::from(id.0))));
+        let _ = self.sched.schedule_in(VDuration(0), Delivery { to: id, from: id, input: Input::Start });
+        id
+    }
+
+    /// Deliver `msg` to `to` at the current instant, from `ProcessId::EXTERNAL`.
+    pub fn post(&mut self, to: ProcessId, msg: M) {
+        let from = ProcessId::EXTERNAL;
+        let _ = self.sched.schedule_in(VDuration(0), Delivery { to, from, input: Input::Message { from, msg } });
+    }
+
+    /// Current virtual time.
+    pub fn now(&self) -> VInstant {
+        self.sched.now()
+    }
+
+    /// The run's root RNG, for randomness drawn outside any process.
+    pub fn rng(&mut self) -> &mut KairosRng {
+        &mut self.rng
+    }
+
+    /// Digest of every delivery so far (time, target, sender, kind) plus anything
+    /// processes `record`ed. Equal digests mean the runs matched.
+    pub fn digest(&self) -> u64 {
+        self.digest.finish()
+    }
+
+    /// Number of pending deliveries and timers.
+    pub fn pending(&self) -> usize {
+        self.sched.len()
+    }
+
+    /// Run every delivery due by `target`, then move the clock there.
+    pub fn run_until(&mut self, target: VInstant) {
+        while self.sched.peek_next().is_some_and(|at| at <= target) {
+            self.deliver_next();
+        }
+        let now = self.sched.now();
+        if target > now {
+            self.sched.clock_mut().advance(target - now);
+        }
+    }
+
+    /// Run for `dur` of virtual time.
+    pub fn run_for(&mut self, dur: VDuration) {
+        let target = self.now() + dur;
+        self.run_until(target);
+    }
+
+    /// Run until nothing is pending or `max_events` deliveries were made.
+    /// Returns the number of deliveries.
+    pub fn run_until_idle(&mut self, max_events: usize) -> usize {
+        let mut delivered = 0;
+        while delivered < max_events && self.deliver_next() {
+            delivered += 1;
+        }
+        delivered
+    }
+
+    fn deliver_next(&mut self) -> bool {
+        let Some(ev) = self.sched.step() else {
+            return false;
+        };
+        let Delivery { to, from, input } = ev.payload;
+        (ev.at.0, to.0, from.0, input.kind()).hash(&mut self.digest);
+        // Messages to unknown processes are dropped.
+        if let Some((process, rng)) = self.procs.get_mut(to.0 as usize) {
+            let mut ctx = SimContext {
+                me: to,
+                sched: &mut self.sched,
+                rng,
+                net: &self.net,
+                digest: &mut self.digest,
+            };
+            process.handle(&mut ctx, input);
+        }
+        true
+    }
+}
@@ -0,0 +1,214 @@
+// kairos-scheduler/src/snapshot.rs
+
+use core::fmt;
+
+use kairos_core::{Clock, VDuration, VInstant};
+
+use crate::codec::{ByteSink, DecodeError, EncodeError, PayloadCodec, Reader};
+use crate::scheduler::{advance_to, Slot};
+use crate::{Event, EventId, Priority, Recurrence, RepeatMode, Scheduler, SnapshotStorage};
+
+const MAGIC: &[u8; 4] = b"KSN1";
+
+const RECURRING: u8 = 1;
+const FIXED_DELAY: u8 = 1 << 1;
+const MAX_REPETITIONS: u8 = 1 << 2;
+const UNTIL: u8 = 1 << 3;
+
+/// Why `Scheduler::restore` rejected a snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestoreError {
+    /// The bytes are not a snapshot, or are truncated or corrupt.
+    Malformed(DecodeError),
+    /// The clock already reads later than the snapshot; clocks cannot go back.
+    ClockAhead { snapshot: VInstant, clock: VInstant },
+    /// The storage backend has no room for every saved event.
+    CapacityFull,
+}
+
+impl From<DecodeError> for RestoreError {
+    fn from(e: DecodeError) -> Self {
+        RestoreError::Malformed(e)
+    }
+}
+
+impl fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestoreError::Malformed(e) => write!(f, "malformed snapshot: {e}"),
+            RestoreError::ClockAhead { snapshot, clock } => {
+                write!(f, "clock reads {}ns, past the snapshot taken at {}ns", clock.0, snapshot.0)
+            }
+            RestoreError::CapacityFull => f.write_str("scheduler storage is full"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RestoreError {}
+
+impl<T: PayloadCodec, C: Clock, S: SnapshotStorage<T>> Scheduler<T, C, S> {
+    /// Checkpoint the scheduler: the clock reading, id and sequence counters, and every
+    /// pending event with its payload and recurrence state.
+    /// The past-deadline policy is configuration, not state, and is not saved.
+    pub fn snapshot<W: ByteSink + ?Sized>(&self, out: &mut W) -> Result<(), EncodeError> {
+        out.put(MAGIC)?;
+        out.put_varint(self.now().0)?;
+        out.put_varint(self.next_id)?;
+        out.put_varint(self.next_seq)?;
+        out.put_varint(self.queue.len() as u64)?;
+        let mut result = Ok(());
+        self.queue.for_each(&mut |ev| {
+            if result.is_ok() {
+                result = encode_event(&ev, out);
+            }
+        });
+        result
+    }
+
+    /// Load a snapshot taken by `snapshot`, replacing any pending events. The clock is
+    /// advanced to the snapshot's instant, and the run then continues exactly as the
+    /// original would have — including ids, tie-breaking and recurring events.
+    ///
+    /// The clock must not read later than the snapshot. The events are decoded and
+    /// checked in empty storages from `SnapshotStorage::empty` before anything is
+    /// replaced, so on error the scheduler is unchanged.
+    ///
+    /// `T: Clone` is needed even for one-shot payloads: a recurring event clones its
+    /// payload on every firing, and whether the snapshot holds one is only known once
+    /// it has been read.
+    pub fn restore(&mut self, bytes: &[u8]) -> Result<(), RestoreError>
+    where
+        T: Clone,
+    {
+        let header = read_header(&mut Reader::new(bytes))?;
+        let clock = self.now();
+        if clock > header.now {
+            return Err(RestoreError::ClockAhead { snapshot: header.now, clock });
+        }
+        {
+            // Sequence numbers make event keys unique, and backends may index events
+            // by key: file each event under its seq to catch repeats before that matters.
+            let mut by_seq = self.queue.empty();
+            decode_events::<T>(bytes, &header, |start, mut ev| {
+                ev.id = EventId(ev.seq);
+                if by_seq.remove(ev.id).is_some() {
+                    return Err(DecodeError { offset: start }.into());
+                }
+                by_seq.push(ev).map_err(|_| RestoreError::CapacityFull)
+            })?;
+        }
+        let mut queue = self.queue.empty();
+        decode_events::<T>(bytes, &header, |start, ev| {
+            // Backends need not detect a repeated id, so look for it before pushing.
+            if queue.remove(ev.id).is_some() {
+                return Err(DecodeError { offset: start }.into());
+            }
+            queue.push(ev).map_err(|_| RestoreError::CapacityFull)
+        })?;
+        self.queue = queue;
+        advance_to(&mut self.clock, header.now);
+        self.next_id = header.next_id;
+        self.next_seq = header.next_seq;
+        Ok(())
+    }
+}
+
+/// The counters in front of a snapshot's events.
+struct Header {
+    now: VInstant,
+    next_id: u64,
+    next_seq: u64,
+    count: u64,
+}
+
+fn read_header(r: &mut Reader<'_>) -> Result<Header, DecodeError> {
+    if r.take(MAGIC.len())? != MAGIC {
+        return Err(DecodeError { offset: 0 });
+    }
+    Ok(Header { now: VInstant(r.varint()?), next_id: r.varint()?, next_seq: r.varint()?, count: r.varint()? })
+}
+
+/// Decode every event of the snapshot in `bytes`, handing each to `f` with its offset.
+fn decode_events<T: PayloadCodec + Clone>(
+    bytes: &[u8],
+    header: &Header,
+    mut f: impl FnMut(usize, Event<Slot<T>>) -> Result<(), RestoreError>,
+) -> Result<(), RestoreError> {
+    let mut r = Reader::new(bytes);
+    read_header(&mut r)?;
+    for _ in 0..header.count {
+        let start = r.position();
+        let ev = decode_event::<T>(&mut r)?;
+        // Ids and sequence numbers come from the saved counters; anything else
+        // would collide with events scheduled after the restore.
+        if ev.id.0 >= header.next_id || ev.seq >= header.next_seq {
+            return Err(DecodeError { offset: start }.into());
+        }
+        f(start, ev)?;
+    }
+    Ok(r.finish()?)
+}
+
+fn encode_event<T: PayloadCodec, W: ByteSink + ?Sized>(ev: &Event<&Slot<T>>, out: &mut W) -> Result<(), EncodeError> {
+    out.put_varint(ev.id.0)?;
+    out.put_varint(ev.at.0)?;
+    out.put(&[ev.priority.0])?;
+    out.put_varint(ev.seq)?;
+    match ev.payload.recurrence() {
+        None => out.put(&[0])?,
+        Some((rule, fired)) => {
+            let mut flags = RECURRING;
+            if rule.mode == RepeatMode::FixedDelay {
+                flags |= FIXED_DELAY;
+            }
+            if rule.max_repetitions.is_some() {
+                flags |= MAX_REPETITIONS;
+            }
+            if rule.until.is_some() {
+                flags |= UNTIL;
+            }
+            out.put(&[flags])?;
+            out.put_varint(rule.period.0)?;
+            out.put_varint(u64::from(fired))?;
+            if let Some(max) = rule.max_repetitions {
+                out.put_varint(u64::from(max))?;
+            }
+            if let Some(until) = rule.until {
+                out.put_varint(until.0)?;
+            }
+        }
+    }
+    ev.payload.payload().encode(out)
+}
+
+fn decode_event<T: PayloadCodec + Clone>(r: &mut Reader<'_>) -> Result<Event<Slot<T>>, DecodeError> {
+    let id = EventId(r.varint()?);
+    let at = VInstant(r.varint()?);
+    let priority = Priority(r.u8()?);
+    let seq = r.varint()?;
+    let flags_at = r.position();
+    let flags = r.u8()?;
+    let repeat = if flags == 0 {
+        None
+    } else if flags & RECURRING == 0 || flags >> 4 != 0 {
+        return Err(DecodeError { offset: flags_at });
+    } else {
+        let mode = if flags & FIXED_DELAY != 0 { RepeatMode::FixedDelay } else { RepeatMode::FixedRate };
+        let period = VDuration(r.varint()?);
+        if period.0 == 0 {
+            return Err(DecodeError { offset: flags_at + 1 });
+        }
+        let fired = u32_varint(r)?;
+        let max_repetitions = if flags & MAX_REPETITIONS != 0 { Some(u32_varint(r)?) } else { None };
+        let until = if flags & UNTIL != 0 { Some(VInstant(r.varint()?)) } else { None };
+        Some((Recurrence { period, mode, max_repetitions, until }, fired))
+    };
+    let payload = T::decode(r)?;
+    Ok(Event { id, at, priority, seq, payload: Slot::restored(payload, repeat) })
+}
+
+fn u32_varint(r: &mut Reader<'_>) -> Result<u32, DecodeError> {
+    let start = r.position();
+    u32::try_from(r.varint()?).map_err(|_| DecodeError { offset: start })
+}
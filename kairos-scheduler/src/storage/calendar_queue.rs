@@ -0,0 +1,207 @@
+// kairos-scheduler/src/storage/calendar_queue.rs
+
+use kairos_core::{VDuration, VInstant};
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+
+use crate::scheduler::{Event, EventId, Key, Slot};
+use super::{SnapshotStorage, Storage};
+
+#[cfg(feature = "std")]
+type Index = std::collections::HashMap<EventId, Key>;
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+type Index = alloc::collections::BTreeMap<EventId, Key>;
+
+const MIN_BUCKETS: usize = 2;
+/// Events sampled to pick a new bucket width on resize.
+const WIDTH_SAMPLE: usize = 25;
+
+/// Calendar queue (R. Brown, 1988) for very large event sets.
+///
+/// Time is cut into windows of `width` nanoseconds, mapped round-robin onto a
+/// power-of-two number of buckets like days onto a year of pages. Each bucket is kept
+/// sorted in `Event` order, so the next event is always the tail of some bucket.
+/// The bucket count doubles or halves with the population and the width is re-tuned
+/// from the spacing of the earliest events, keeping buckets short: enqueue and dequeue
+/// are amortized O(1) for the usual "hold" workloads of discrete-event simulation.
+pub struct CalendarQueueStorage<T> {
+    /// Bucket `i` holds windows `i`, `i + n`, `i + 2n`, …, sorted so the next to fire is last.
+    buckets: Vec<Vec<Event<Slot<T>>>>,
+    width: u64,
+    /// Window of the last dequeue; no pending event is in an earlier window.
+    window: u64,
+    index: Index,
+}
+
+impl<T> CalendarQueueStorage<T> {
+    /// Starts with two 1 µs buckets; both adapt as events come and go.
+    pub fn new() -> Self {
+        let mut buckets = Vec::with_capacity(MIN_BUCKETS);
+        buckets.resize_with(MIN_BUCKETS, Vec::new);
+        Self { buckets, width: 1_000, window: 0, index: Index::new() }
+    }
+
+    /// Current bucket width.
+    pub fn bucket_width(&self) -> VDuration {
+        VDuration(self.width)
+    }
+
+    /// Current number of buckets.
+    pub fn bucket_count(&self) -> usize {
+        self.buckets.len()
+    }
+
+    #[inline(always)]
+    fn window_of(&self, at: VInstant) -> u64 {
+        at.0 / self.width
+    }
+
+    #[inline(always)]
+    fn bucket_of(&self, window: u64) -> usize {
+        (window as usize) & (self.buckets.len() - 1)
+    }
+
+    /// Window holding the next event to fire.
+    fn next_window(&self) -> Option<u64> {
+        if self.index.is_empty() {
+            return None;
+        }
+        let year = self.window.saturating_add(self.buckets.len() as u64);
+        for w in self.window..year {
+            if let Some(ev) = self.buckets[self.bucket_of(w)].last() {
+                if self.window_of(ev.at) == w {
+                    return Some(w);
+                }
+            }
+        }
+        // Nothing within a year: the earliest bucket tail is the next event.
+        self.buckets.iter().filter_map(|b| b.last()).max().map(|ev| self.window_of(ev.at))
+    }
+
+    fn insert(&mut self, ev: Event<Slot<T>>) {
+        let window = self.window_of(ev.at);
+        if window < self.window {
+            self.window = window;
+        }
+        let key = ev.key();
+        let bucket = self.bucket_of(window);
+        let bucket = &mut self.buckets[bucket];
+        let pos = bucket.partition_point(|probe| probe.key() > key);
+        bucket.insert(pos, ev);
+    }
+
+    /// Rebuild with `n` buckets and a width tuned to the earliest events.
+    fn resize(&mut self, n: usize) {
+        let mut events: Vec<Event<Slot<T>>> = Vec::with_capacity(self.index.len());
+        for bucket in &mut self.buckets {
+            events.append(bucket);
+        }
+        self.width = sampled_width(&mut events).unwrap_or(self.width);
+        self.buckets.clear();
+        self.buckets.resize_with(n, Vec::new);
+        self.window = events.iter().map(|ev| ev.at.0 / self.width).min().unwrap_or(0);
+        for ev in events {
+            let bucket = self.bucket_of(self.window_of(ev.at));
+            self.buckets[bucket].push(ev);
+        }
+        for bucket in &mut self.buckets {
+            bucket.sort_unstable();
+        }
+    }
+
+    fn grow_or_shrink(&mut self) {
+        let (len, n) = (self.index.len(), self.buckets.len());
+        if len > 2 * n {
+            self.resize(2 * n);
+        } else if n > MIN_BUCKETS && len < n / 2 {
+            self.resize(n / 2);
+        }
+    }
+}
+
+/// Brown's heuristic: three times the average gap between the earliest events,
+/// ignoring gaps more than twice the average. `None` if the sample has no spread.
+fn sampled_width<T>(events: &mut [Event<Slot<T>>]) -> Option<u64> {
+    let k = events.len().min(WIDTH_SAMPLE);
+    if k < 2 {
+        return None;
+    }
+    // Greatest = next to fire, so the earliest events end up at the back.
+    let split = events.len() - k;
+    if split > 0 {
+        events.select_nth_unstable(split);
+    }
+    events[split..].sort_unstable_by_key(|ev| ev.at);
+    let sample = &events[split..];
+    let avg = (sample[k - 1].at.0 - sample[0].at.0) / (k as u64 - 1);
+    let (sum, count) = sample
+        .windows(2)
+        .map(|w| w[1].at.0 - w[0].at.0)
+        .filter(|&g| g <= 2 * avg)
+        .fold((0u64, 0u64), |(s, c), g| (s + g, c + 1));
+    let avg = sum.checked_div(count).unwrap_or(avg);
+    (avg > 0).then(|| avg.saturating_mul(3))
+}
+
+impl<T> Default for CalendarQueueStorage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Storage<T> for CalendarQueueStorage<T> {
+    fn push(&mut self, event: Event<Slot<T>>) -> Result<(), Event<Slot<T>>> {
+        self.index.insert(event.id, event.key());
+        self.insert(event);
+        self.grow_or_shrink();
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<VInstant> {
+        let w = self.next_window()?;
+        self.buckets[self.bucket_of(w)].last().map(|ev| ev.at)
+    }
+
+    fn pop(&mut self) -> Option<Event<Slot<T>>> {
+        let w = self.next_window()?;
+        self.window = w;
+        let bucket = self.bucket_of(w);
+        let ev = self.buckets[bucket].pop()?;
+        self.index.remove(&ev.id);
+        self.grow_or_shrink();
+        Some(ev)
+    }
+
+    fn remove(&mut self, id: EventId) -> Option<Event<Slot<T>>> {
+        let key = self.index.remove(&id)?;
+        let bucket = self.bucket_of(self.window_of(key.at));
+        let bucket = &mut self.buckets[bucket];
+        let pos = bucket.binary_search_by(|probe| key.cmp(&probe.key())).ok()?;
+        let ev = bucket.remove(pos);
+        self.grow_or_shrink();
+        Some(ev)
+    }
+
+    fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    fn clear(&mut self) {
+        for bucket in &mut self.buckets {
+            bucket.clear();
+        }
+        self.index.clear();
+        self.window = 0;
+    }
+}
+
+impl<T> SnapshotStorage<T> for CalendarQueueStorage<T> {
+    fn for_each(&self, f: &mut dyn FnMut(Event<&Slot<T>>)) {
+        self.buckets.iter().flatten().for_each(|ev| f(ev.as_ref()));
+    }
+
+    fn empty(&self) -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,114 @@
+// kairos-scheduler/src/storage/heapless.rs
+
+use heapless::Vec;
+use kairos_core::VInstant;
+
+use crate::scheduler::{Event, EventId, Slot};
+use super::{SnapshotStorage, Storage};
+
+/// Fixed-capacity storage for `no_std` targets without an allocator.
+/// A binary heap over an inline array of `N` events: O(log n) insert and pop,
+/// O(n) cancel (a linear search, fine for the small `N` this is meant for).
+/// Cancelled events free their room immediately.
+pub struct HeaplessStorage<T, const N: usize> {
+    heap: Vec<Event<Slot<T>>, N>,
+}
+
+impl<T, const N: usize> HeaplessStorage<T, N> {
+    pub const fn new() -> Self {
+        Self { heap: Vec::new() }
+    }
+
+    /// Maximum number of pending events.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i] <= self.heap[parent] {
+                break;
+            }
+            self.heap.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        let len = self.heap.len();
+        loop {
+            let (left, right) = (2 * i + 1, 2 * i + 2);
+            let mut top = i;
+            if left < len && self.heap[left] > self.heap[top] {
+                top = left;
+            }
+            if right < len && self.heap[right] > self.heap[top] {
+                top = right;
+            }
+            if top == i {
+                break;
+            }
+            self.heap.swap(i, top);
+            i = top;
+        }
+    }
+
+    /// Remove the entry at heap index `i`, restoring the heap property.
+    fn take(&mut self, i: usize) -> Event<Slot<T>> {
+        let ev = self.heap.swap_remove(i);
+        if i < self.heap.len() {
+            self.sift_down(i);
+            self.sift_up(i);
+        }
+        ev
+    }
+}
+
+impl<T, const N: usize> Default for HeaplessStorage<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Storage<T> for HeaplessStorage<T, N> {
+    fn push(&mut self, event: Event<Slot<T>>) -> Result<(), Event<Slot<T>>> {
+        self.heap.push(event)?;
+        self.sift_up(self.heap.len() - 1);
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<VInstant> {
+        self.heap.first().map(|ev| ev.at)
+    }
+
+    fn pop(&mut self) -> Option<Event<Slot<T>>> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        Some(self.take(0))
+    }
+
+    fn remove(&mut self, id: EventId) -> Option<Event<Slot<T>>> {
+        let i = self.heap.iter().position(|ev| ev.id == id)?;
+        Some(self.take(i))
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<T, const N: usize> SnapshotStorage<T> for HeaplessStorage<T, N> {
+    fn for_each(&self, f: &mut dyn FnMut(Event<&Slot<T>>)) {
+        self.heap.iter().for_each(|ev| f(ev.as_ref()));
+    }
+
+    fn empty(&self) -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,57 @@
+// kairos-scheduler/src/storage/mod.rs
+
+use kairos_core::VInstant;
+use crate::scheduler::{Event, EventId, Slot};
+
+/// Backing store for a `Scheduler`'s pending events.
+///
+/// Implementations hold `Event<Slot<T>>` values as given and must pop them in `Event`
+/// order — earliest deadline, then priority, then insertion sequence — so that every
+/// backend fires events identically. `Event`'s `Ord` already encodes that order
+/// (greatest = next to fire), so a max-heap of events is a valid implementation.
+pub trait Storage<T> {
+    /// Insert an event; hands it back if there is no room.
+    fn push(&mut self, event: Event<Slot<T>>) -> Result<(), Event<Slot<T>>>;
+    /// Deadline of the next event to fire.
+    fn peek(&self) -> Option<VInstant>;
+    /// Remove the next event to fire.
+    fn pop(&mut self) -> Option<Event<Slot<T>>>;
+    /// Remove a pending event by id, freeing its room immediately.
+    fn remove(&mut self, id: EventId) -> Option<Event<Slot<T>>>;
+    /// Number of pending events.
+    fn len(&self) -> usize;
+    /// Whether no events are pending.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Drop every pending event.
+    fn clear(&mut self);
+}
+
+/// A `Storage` that can list its pending events and start over empty, as
+/// `Scheduler::snapshot` and `Scheduler::restore` require. Backends without snapshots
+/// need only `Storage`.
+pub trait SnapshotStorage<T>: Storage<T> {
+    /// Visit every pending event, in no particular order.
+    fn for_each(&self, f: &mut dyn FnMut(Event<&Slot<T>>));
+    /// A new, empty storage configured like this one (same capacity or resolution).
+    fn empty(&self) -> Self
+    where
+        Self: Sized;
+}
+
+pub mod heapless;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub mod pq;
+#[cfg(all(feature = "timer-wheel", any(feature = "std", feature = "alloc")))]
+pub mod wheel;
+#[cfg(all(feature = "calendar-queue", any(feature = "std", feature = "alloc")))]
+pub mod calendar_queue;
+
+pub use self::heapless::HeaplessStorage;
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub use pq::PqStorage;
+#[cfg(all(feature = "timer-wheel", any(feature = "std", feature = "alloc")))]
+pub use wheel::WheelStorage;
+#[cfg(all(feature = "calendar-queue", any(feature = "std", feature = "alloc")))]
+pub use calendar_queue::CalendarQueueStorage;
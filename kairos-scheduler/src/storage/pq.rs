@@ -0,0 +1,90 @@
+// kairos-scheduler/src/storage/pq.rs
+
+use core::cmp::Reverse;
+use kairos_core::VInstant;
+use priority_queue::PriorityQueue;
+
+use crate::scheduler::{Event, EventId, Key, Slot};
+use super::{SnapshotStorage, Storage};
+
+#[cfg(feature = "std")]
+type Queue = PriorityQueue<EventId, Reverse<Key>>;
+#[cfg(feature = "std")]
+type Payloads<T> = std::collections::HashMap<EventId, Slot<T>>;
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+type Queue = PriorityQueue<EventId, Reverse<Key>, ahash::RandomState>;
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+type Payloads<T> = alloc::collections::BTreeMap<EventId, Slot<T>>;
+
+/// Default heap-allocated storage: an indexed priority queue keyed by `EventId`
+/// plus a payload map. O(log n) insert, pop and cancel; unbounded capacity.
+pub struct PqStorage<T> {
+    pq: Queue,
+    events: Payloads<T>,
+}
+
+impl<T> PqStorage<T> {
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            pq: PriorityQueue::new(),
+            #[cfg(all(not(feature = "std"), feature = "alloc"))]
+            pq: PriorityQueue::with_hasher(ahash::RandomState::new()),
+            events: Payloads::new(),
+        }
+    }
+}
+
+impl<T> Default for PqStorage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Storage<T> for PqStorage<T> {
+    fn push(&mut self, event: Event<Slot<T>>) -> Result<(), Event<Slot<T>>> {
+        self.pq.push(event.id, Reverse(event.key()));
+        self.events.insert(event.id, event.payload);
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<VInstant> {
+        self.pq.peek().map(|(_, Reverse(key))| key.at)
+    }
+
+    fn pop(&mut self) -> Option<Event<Slot<T>>> {
+        let (id, Reverse(key)) = self.pq.pop()?;
+        let payload = self.events.remove(&id)?;
+        Some(Event::from_key(id, key, payload))
+    }
+
+    fn remove(&mut self, id: EventId) -> Option<Event<Slot<T>>> {
+        let (id, Reverse(key)) = self.pq.remove(&id)?;
+        let payload = self.events.remove(&id)?;
+        Some(Event::from_key(id, key, payload))
+    }
+
+    fn len(&self) -> usize {
+        self.pq.len()
+    }
+
+    fn clear(&mut self) {
+        self.pq.clear();
+        self.events.clear();
+    }
+}
+
+impl<T> SnapshotStorage<T> for PqStorage<T> {
+    fn for_each(&self, f: &mut dyn FnMut(Event<&Slot<T>>)) {
+        for (&id, &Reverse(key)) in self.pq.iter() {
+            if let Some(payload) = self.events.get(&id) {
+                f(Event::from_key(id, key, payload));
+            }
+        }
+    }
+
+    fn empty(&self) -> Self {
+        Self::new()
+    }
+}
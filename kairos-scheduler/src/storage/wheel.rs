@@ -0,0 +1,237 @@
+// kairos-scheduler/src/storage/wheel.rs
+
+use core::mem;
+use kairos_core::{VDuration, VInstant};
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::scheduler::{Event, EventId, Key, Slot};
+use super::{SnapshotStorage, Storage};
+
+#[cfg(feature = "std")]
+type Index = std::collections::HashMap<EventId, Loc>;
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+type Index = BTreeMap<EventId, Loc>;
+
+/// Slots per wheel level (6 bits of the tick counter).
+const SLOTS: usize = 64;
+const SLOT_BITS: u32 = 6;
+/// Enough levels to cover every `u64` tick; more would never be used.
+const MAX_LEVELS: usize = 11;
+
+/// Where a pending event currently lives.
+#[derive(Clone, Copy)]
+enum Loc {
+    Ready(Key),
+    Wheel { slot: usize, pos: usize },
+    Overflow(Key),
+}
+
+/// Hierarchical timing wheel for many short-lived timers.
+///
+/// Deadlines are bucketed into `tick`-sized steps across `levels` wheels of 64 slots,
+/// each level 64× coarser than the one below. Insert and cancel are O(1); buckets are
+/// cascaded down lazily as the wheel turns. Deadlines past the last level wait in an
+/// ordered overflow list. Events whose tick has come are kept sorted by `Event` order,
+/// so firing order matches every other backend regardless of the tick resolution.
+pub struct WheelStorage<T> {
+    tick: u64,
+    levels: usize,
+    /// Current tick: everything in `ready` is due at or before it, everything in the
+    /// wheel strictly after it.
+    cursor: u64,
+    slots: Vec<Vec<Event<Slot<T>>>>,
+    /// One bit per non-empty slot, one word per level.
+    occupied: Vec<u64>,
+    ready: BTreeMap<Key, (EventId, Slot<T>)>,
+    overflow: BTreeMap<Key, (EventId, Slot<T>)>,
+    index: Index,
+}
+
+impl<T> WheelStorage<T> {
+    /// 1 µs ticks over 6 levels: a little over 19 hours before deadlines overflow.
+    pub fn new() -> Self {
+        Self::with_resolution(VDuration::from_micros(1), 6)
+    }
+
+    /// Custom tick resolution and number of levels (clamped to `1..=11`).
+    /// The wheel spans `tick × 64^levels`; later deadlines go to the overflow list.
+    pub fn with_resolution(tick: VDuration, levels: usize) -> Self {
+        let levels = levels.clamp(1, MAX_LEVELS);
+        let mut slots = Vec::with_capacity(levels * SLOTS);
+        slots.resize_with(levels * SLOTS, Vec::new);
+        Self {
+            tick: tick.0.max(1),
+            levels,
+            cursor: 0,
+            slots,
+            occupied: vec![0; levels],
+            ready: BTreeMap::new(),
+            overflow: BTreeMap::new(),
+            index: Index::new(),
+        }
+    }
+
+    /// Wheel level for `tick`, relative to the cursor; `None` if already due.
+    fn level_of(&self, tick: u64) -> Option<usize> {
+        if tick <= self.cursor {
+            return None;
+        }
+        let msb = 63 - (tick ^ self.cursor).leading_zeros();
+        Some((msb / SLOT_BITS) as usize)
+    }
+
+    fn place(&mut self, ev: Event<Slot<T>>) {
+        let tick = ev.at.0 / self.tick;
+        let key = ev.key();
+        match self.level_of(tick) {
+            None => {
+                self.index.insert(ev.id, Loc::Ready(key));
+                self.ready.insert(key, (ev.id, ev.payload));
+            }
+            Some(level) if level >= self.levels => {
+                self.index.insert(ev.id, Loc::Overflow(key));
+                self.overflow.insert(key, (ev.id, ev.payload));
+            }
+            Some(level) => {
+                let idx = ((tick >> (SLOT_BITS * level as u32)) as usize) & (SLOTS - 1);
+                let slot = level * SLOTS + idx;
+                self.index.insert(ev.id, Loc::Wheel { slot, pos: self.slots[slot].len() });
+                self.slots[slot].push(ev);
+                self.occupied[level] |= 1 << idx;
+            }
+        }
+    }
+
+    /// Lowest non-empty slot: it holds the earliest deadlines in the wheel.
+    fn first_slot(&self) -> Option<(usize, usize)> {
+        self.occupied
+            .iter()
+            .enumerate()
+            .find(|(_, bits)| **bits != 0)
+            .map(|(level, bits)| (level, bits.trailing_zeros() as usize))
+    }
+
+    /// Move overflowed events that the cursor has caught up with into the wheel.
+    fn migrate_overflow(&mut self) {
+        while let Some((key, _)) = self.overflow.first_key_value() {
+            let tick = key.at.0 / self.tick;
+            if self.level_of(tick).is_some_and(|level| level >= self.levels) {
+                break;
+            }
+            let Some((key, (id, payload))) = self.overflow.pop_first() else {
+                break;
+            };
+            self.place(Event::from_key(id, key, payload));
+        }
+    }
+
+    /// Turn the wheel until some event is ready (or nothing is left).
+    fn refill(&mut self) {
+        while self.ready.is_empty() {
+            match self.first_slot() {
+                Some((level, idx)) => {
+                    let shift = SLOT_BITS * (level as u32 + 1);
+                    let high = self.cursor.checked_shr(shift).map_or(0, |h| h << shift);
+                    self.cursor = high | ((idx as u64) << (SLOT_BITS * level as u32));
+                    self.migrate_overflow();
+                    self.occupied[level] &= !(1 << idx);
+                    let slot = level * SLOTS + idx;
+                    for ev in mem::take(&mut self.slots[slot]) {
+                        self.place(ev);
+                    }
+                }
+                None => {
+                    let Some((key, _)) = self.overflow.first_key_value() else {
+                        return;
+                    };
+                    self.cursor = key.at.0 / self.tick;
+                    self.migrate_overflow();
+                }
+            }
+        }
+    }
+}
+
+impl<T> Default for WheelStorage<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Storage<T> for WheelStorage<T> {
+    fn push(&mut self, event: Event<Slot<T>>) -> Result<(), Event<Slot<T>>> {
+        self.place(event);
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<VInstant> {
+        if let Some((key, _)) = self.ready.first_key_value() {
+            return Some(key.at);
+        }
+        match self.first_slot() {
+            Some((level, idx)) => self.slots[level * SLOTS + idx].iter().max().map(|ev| ev.at),
+            None => self.overflow.first_key_value().map(|(key, _)| key.at),
+        }
+    }
+
+    fn pop(&mut self) -> Option<Event<Slot<T>>> {
+        self.refill();
+        let (key, (id, payload)) = self.ready.pop_first()?;
+        self.index.remove(&id);
+        Some(Event::from_key(id, key, payload))
+    }
+
+    fn remove(&mut self, id: EventId) -> Option<Event<Slot<T>>> {
+        match self.index.remove(&id)? {
+            Loc::Ready(key) => {
+                let (id, payload) = self.ready.remove(&key)?;
+                Some(Event::from_key(id, key, payload))
+            }
+            Loc::Overflow(key) => {
+                let (id, payload) = self.overflow.remove(&key)?;
+                Some(Event::from_key(id, key, payload))
+            }
+            Loc::Wheel { slot, pos } => {
+                let bucket = &mut self.slots[slot];
+                let ev = bucket.swap_remove(pos);
+                if let Some(moved) = bucket.get(pos) {
+                    self.index.insert(moved.id, Loc::Wheel { slot, pos });
+                } else if bucket.is_empty() {
+                    self.occupied[slot / SLOTS] &= !(1 << (slot % SLOTS));
+                }
+                Some(ev)
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    fn clear(&mut self) {
+        for bucket in &mut self.slots {
+            bucket.clear();
+        }
+        self.occupied.iter_mut().for_each(|bits| *bits = 0);
+        self.ready.clear();
+        self.overflow.clear();
+        self.index.clear();
+    }
+}
+
+impl<T> SnapshotStorage<T> for WheelStorage<T> {
+    fn for_each(&self, f: &mut dyn FnMut(Event<&Slot<T>>)) {
+        for (&key, (id, payload)) in self.ready.iter().chain(&self.overflow) {
+            f(Event::from_key(*id, key, payload));
+        }
+        self.slots.iter().flatten().for_each(|ev| f(ev.as_ref()));
+    }
+
+    fn empty(&self) -> Self {
+        Self::with_resolution(VDuration(self.tick), self.levels)
+    }
+}
@@ -0,0 +1,221 @@
+// kairos-scheduler/src/trace.rs
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+
+use kairos_core::{Clock, VInstant};
+
+use crate::codec::{put_varint, DecodeError, Reader};
+use crate::digest::Fnv64;
+use crate::scheduler::advance_to;
+use crate::{EventId, Scheduler, Storage};
+
+const MAGIC: &[u8; 4] = b"KTR1";
+
+/// One fired event: its id, when it fired and a digest of its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub id: EventId,
+    pub at: VInstant,
+    pub digest: u64,
+}
+
+impl TraceEntry {
+    /// Entry for `payload` fired as `id` at `at`. The digest (FNV-1a over the payload's
+    /// `Hash`) is stable across platforms and runs.
+    pub fn new<T: Hash + ?Sized>(id: EventId, at: VInstant, payload: &T) -> Self {
+        let mut h = Fnv64::new();
+        payload.hash(&mut h);
+        Self { id, at, digest: h.finish() }
+    }
+}
+
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "id {} at {}ns digest {:#018x}", self.id.0, self.at.0, self.digest)
+    }
+}
+
+/// Fired events of a run, in firing order.
+///
+/// Record one with `Scheduler::run_until_recorded`, store it with `to_bytes`, and check
+/// later runs against it with `Scheduler::run_until_verified`: the first event that
+/// differs is reported as a `Divergence`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    entries: Vec<TraceEntry>,
+}
+
+impl Trace {
+    pub const fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    pub fn push(&mut self, entry: TraceEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Digest of the whole trace, for a one-number comparison.
+    pub fn digest(&self) -> u64 {
+        let mut h = Fnv64::new();
+        for e in &self.entries {
+            (e.id.0, e.at.0, e.digest).hash(&mut h);
+        }
+        h.finish()
+    }
+
+    /// Checks a run against this trace, one fired event at a time.
+    pub fn verifier(&self) -> Verifier<'_> {
+        Verifier { expected: &self.entries, pos: 0 }
+    }
+
+    /// First entry where `actual` departs from this trace, if any.
+    pub fn diff(&self, actual: &Trace) -> Option<Divergence> {
+        let mut v = self.verifier();
+        actual.entries.iter().try_for_each(|&e| v.check(e)).err().or_else(|| v.finish().err())
+    }
+
+    /// Compact binary form: ids and instant deltas as varints, digests as 8 raw bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(MAGIC.len() + 1 + self.entries.len() * 12);
+        out.extend_from_slice(MAGIC);
+        put_varint(&mut out, self.entries.len() as u64);
+        let mut prev = 0u64;
+        for e in &self.entries {
+            put_varint(&mut out, e.id.0);
+            // Instants never go backwards within a run, so deltas stay small.
+            put_varint(&mut out, e.at.0.wrapping_sub(prev));
+            out.extend_from_slice(&e.digest.to_le_bytes());
+            prev = e.at.0;
+        }
+        out
+    }
+
+    /// Parses the output of `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut r = Reader::new(bytes);
+        if r.take(MAGIC.len())? != MAGIC {
+            return Err(DecodeError { offset: 0 });
+        }
+        let count = r.varint()?;
+        // Each entry takes at least 10 bytes; reject counts the input cannot hold.
+        if count > (bytes.len() / 10) as u64 {
+            return Err(r.error());
+        }
+        let mut entries = Vec::with_capacity(count as usize);
+        let mut prev = 0u64;
+        for _ in 0..count {
+            let id = EventId(r.varint()?);
+            let at = prev.wrapping_add(r.varint()?);
+            let digest = r.u64_le()?;
+            entries.push(TraceEntry { id, at: VInstant(at), digest });
+            prev = at;
+        }
+        r.finish()?;
+        Ok(Self { entries })
+    }
+}
+
+/// Where a run departed from its recorded trace.
+/// `expected` is `None` when the run fired more events than were recorded,
+/// `actual` is `None` when it stopped short.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    /// Position of the first mismatching event, counting from 0.
+    pub index: usize,
+    pub expected: Option<TraceEntry>,
+    pub actual: Option<TraceEntry>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "run diverged from trace at event #{}", self.index)?;
+        match self.expected {
+            Some(e) => writeln!(f, "  - expected: {e}")?,
+            None => writeln!(f, "  - expected: end of trace")?,
+        }
+        match self.actual {
+            Some(a) => write!(f, "  + actual:   {a}"),
+            None => write!(f, "  + actual:   end of run"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Divergence {}
+
+/// Compares fired events against a recorded `Trace` as they happen.
+#[derive(Debug, Clone)]
+pub struct Verifier<'a> {
+    expected: &'a [TraceEntry],
+    pos: usize,
+}
+
+impl Verifier<'_> {
+    /// Number of events matched so far.
+    pub fn matched(&self) -> usize {
+        self.pos
+    }
+
+    /// Match the next fired event.
+    pub fn check(&mut self, actual: TraceEntry) -> Result<(), Divergence> {
+        let expected = self.expected.get(self.pos).copied();
+        if expected != Some(actual) {
+            return Err(Divergence { index: self.pos, expected, actual: Some(actual) });
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Fails if the trace holds events the run never fired.
+    pub fn finish(&self) -> Result<(), Divergence> {
+        match self.expected.get(self.pos) {
+            Some(&e) => Err(Divergence { index: self.pos, expected: Some(e), actual: None }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: Hash, C: Clock, S: Storage<T>> Scheduler<T, C, S> {
+    /// `run_until`, appending every fired event to `trace`.
+    pub fn run_until_recorded<F: FnMut(VInstant, T)>(&mut self, target: VInstant, trace: &mut Trace, mut on_event: F) {
+        while let Some((now, ev)) = self.dispatch_next(target) {
+            trace.push(TraceEntry::new(ev.id, now, &ev.payload));
+            on_event(now, ev.payload);
+        }
+        advance_to(self.clock_mut(), target);
+    }
+
+    /// `run_until`, checking every fired event against `verifier`.
+    /// Stops at the first mismatch, before handing that event to `on_event`, and
+    /// leaves the clock at it. Call `Verifier::finish` once the run is over to also
+    /// catch recorded events that never fired.
+    pub fn run_until_verified<F: FnMut(VInstant, T)>(
+        &mut self,
+        target: VInstant,
+        verifier: &mut Verifier<'_>,
+        mut on_event: F,
+    ) -> Result<(), Divergence> {
+        while let Some((now, ev)) = self.dispatch_next(target) {
+            verifier.check(TraceEntry::new(ev.id, now, &ev.payload))?;
+            on_event(now, ev.payload);
+        }
+        advance_to(self.clock_mut(), target);
+        Ok(())
+    }
+}
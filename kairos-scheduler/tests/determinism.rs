@@ -10,10 +10,13 @@ macro_rules! make_scheduler {
     };
 }
 
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+use kairos_scheduler::storage::HeaplessStorage;
+
 #[cfg(not(any(feature = "std", feature = "alloc")))]
 macro_rules! make_scheduler {
     ($payload_type:ty, $clock:expr) => {
-        Scheduler::<$payload_type, _, 16>::new($clock)
+        Scheduler::<$payload_type, _, HeaplessStorage<$payload_type, 16>>::new($clock)
     };
 }
 
@@ -50,3 +53,55 @@ fn test_determinism_manual_clock() {
         assert_eq!(first_hash, hash);
     }
 }
+
+fn same_instant_order() -> Vec<u32> {
+    let mut scheduler = make_scheduler!(u32, ManualClock::new());
+
+    for payload in 0..5 {
+        let _ = scheduler.schedule_in(VDuration::from_secs(1), payload).unwrap();
+    }
+    // A cancelled event in between must not disturb the order of later insertions.
+    let early = scheduler.schedule_in(VDuration::from_millis(500), 5).unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_secs(1), 6).unwrap();
+    scheduler.cancel(early).unwrap();
+    let _ = scheduler.schedule_at(kairos_core::VInstant(VDuration::from_secs(1).0), 7).unwrap();
+
+    let mut order = Vec::new();
+    let target_time = scheduler.now() + VDuration::from_secs(1);
+    scheduler.run_until(target_time, |_, payload| order.push(payload));
+    order
+}
+
+#[test]
+fn test_same_instant_fifo_order() {
+    let first = same_instant_order();
+    assert_eq!(first, vec![0, 1, 2, 3, 4, 6, 7]);
+    for _ in 1..100 {
+        assert_eq!(same_instant_order(), first);
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[test]
+fn test_determinism_against_recorded_trace() {
+    use kairos_scheduler::Trace;
+
+    let run = |trace: &mut Trace| {
+        let mut scheduler = make_scheduler!(u32, ManualClock::new());
+        for (i, s) in [3u64, 1, 2, 1].into_iter().enumerate() {
+            let _ = scheduler.schedule_in(VDuration::from_secs(s), i as u32).unwrap();
+        }
+        let target_time = scheduler.now() + VDuration::from_secs(5);
+        scheduler.run_until_recorded(target_time, trace, |_, _| {});
+    };
+
+    let mut expected = Trace::new();
+    run(&mut expected);
+    for _ in 1..100 {
+        let mut actual = Trace::new();
+        run(&mut actual);
+        if let Some(divergence) = expected.diff(&actual) {
+            panic!("{divergence}");
+        }
+    }
+}
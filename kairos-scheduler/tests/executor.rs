@@ -0,0 +1,96 @@
+#![cfg(feature = "async")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use kairos_core::{ManualClock, VDuration, VInstant};
+use kairos_scheduler::Executor;
+
+fn secs(s: u64) -> VInstant {
+    VInstant(VDuration::from_secs(s).0)
+}
+
+#[test]
+fn sleep_advances_virtual_time() {
+    let mut exec = Executor::new(ManualClock::new());
+    let timer = exec.timer();
+
+    let woke_at = exec.block_on(async move {
+        timer.sleep(VDuration::from_secs(3)).await;
+        timer.now()
+    });
+
+    assert_eq!(woke_at, Some(secs(3)));
+    assert_eq!(exec.now(), secs(3));
+}
+
+#[test]
+fn tasks_interleave_in_deadline_order() {
+    let mut exec = Executor::new(ManualClock::new());
+    let log = Rc::new(RefCell::new(Vec::new()));
+
+    for (name, delays) in [("a", [2, 2, 2]), ("b", [1, 3, 1]), ("c", [5, 0, 0])] {
+        let timer = exec.timer();
+        let log = Rc::clone(&log);
+        exec.spawn(async move {
+            for d in delays {
+                timer.sleep(VDuration::from_secs(d)).await;
+                log.borrow_mut().push((timer.now().0 / 1_000_000_000, name));
+            }
+        });
+    }
+    exec.run();
+
+    // Sleepers due at the same instant wake in the order they went to sleep;
+    // a zero-length sleep completes without yielding.
+    assert_eq!(
+        *log.borrow(),
+        vec![(1, "b"), (2, "a"), (4, "b"), (4, "a"), (5, "c"), (5, "c"), (5, "c"), (5, "b"), (6, "a")]
+    );
+    assert_eq!(exec.pending_tasks(), 0);
+    assert_eq!(exec.now(), secs(6));
+}
+
+#[test]
+fn interval_ticks_at_fixed_rate() {
+    let mut exec = Executor::new(ManualClock::new());
+    let timer = exec.timer();
+    let ticks = Rc::new(RefCell::new(Vec::new()));
+
+    let seen = Rc::clone(&ticks);
+    exec.spawn(async move {
+        let mut interval = timer.interval(VDuration::from_millis(250));
+        loop {
+            let at = interval.tick().await;
+            seen.borrow_mut().push(at.0 / 1_000_000);
+        }
+    });
+    exec.run_until(secs(1));
+
+    assert_eq!(*ticks.borrow(), vec![250, 500, 750, 1000]);
+    assert_eq!(exec.pending_tasks(), 1);
+}
+
+#[test]
+fn dropped_sleep_cancels_its_timer() {
+    let mut exec = Executor::new(ManualClock::new());
+    let timer = exec.timer();
+
+    let out = exec.block_on(async move {
+        let long = timer.sleep(VDuration::from_secs(60));
+        drop(long);
+        timer.sleep(VDuration::from_secs(1)).await;
+        timer.now()
+    });
+    assert_eq!(out, Some(secs(1)));
+
+    // Nothing else is pending: the clock stays where the last sleeper woke.
+    exec.run();
+    assert_eq!(exec.now(), secs(1));
+}
+
+#[test]
+fn block_on_reports_deadlock() {
+    let mut exec = Executor::new(ManualClock::new());
+    assert_eq!(exec.block_on(std::future::pending::<()>()), None);
+}
@@ -1,5 +1,5 @@
 use kairos_core::{ManualClock, VDuration, VInstant, Clock};
-use kairos_scheduler::{Scheduler, Event, EventId};
+use kairos_scheduler::{Scheduler, SchedulerError, Event, EventId, Priority, Recurrence, RepeatMode, PastDeadlinePolicy};
 
 #[cfg(any(feature = "std", feature = "alloc"))]
 macro_rules! make_scheduler {
@@ -8,10 +8,13 @@ macro_rules! make_scheduler {
     };
 }
 
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+use kairos_scheduler::storage::HeaplessStorage;
+
 #[cfg(not(any(feature = "std", feature = "alloc")))]
 macro_rules! make_scheduler {
     ($payload_type:ty, $clock:expr) => {
-        Scheduler::<$payload_type, _, 16>::new($clock)
+        Scheduler::<$payload_type, _, HeaplessStorage<$payload_type, 16>>::new($clock)
     };
 }
 
@@ -77,22 +80,41 @@ fn event_ordering() {
     let event1 = Event {
         id: EventId(0),
         at: VInstant(100),
+        priority: Priority::NORMAL,
+        seq: 0,
         payload: "event1",
     };
     let event2 = Event {
         id: EventId(1),
         at: VInstant(200),
+        priority: Priority::NORMAL,
+        seq: 1,
         payload: "event2",
     };
     let event3 = Event {
         id: EventId(2),
         at: VInstant(100),
+        priority: Priority::NORMAL,
+        seq: 2,
         payload: "event3",
     };
 
     assert!(event1 > event2);
     assert!(event2 < event1);
-    assert_eq!(event1, event3);
+    // Same instant: the earlier insertion pops first.
+    assert!(event1 > event3);
+    assert!(event3 > event2);
+
+    // ...unless priorities differ: lower values pop first.
+    let urgent = Event {
+        id: EventId(3),
+        at: VInstant(100),
+        priority: Priority::HIGH,
+        seq: 3,
+        payload: "urgent",
+    };
+    assert!(urgent > event1);
+    assert!(urgent > event2);
 }
 
 #[test]
@@ -113,7 +135,7 @@ fn event_cancellation() {
     let event_to_cancel = scheduler.schedule_in(VDuration::from_secs(5), "cancelled").unwrap();
     let _ = scheduler.schedule_in(VDuration::from_secs(10), "not_cancelled").unwrap();
 
-    scheduler.cancel(event_to_cancel);
+    scheduler.cancel(event_to_cancel).unwrap();
 
     let mut processed_events = Vec::new();
     let target_time = scheduler.now() + VDuration::from_secs(20);
@@ -127,13 +149,12 @@ fn event_cancellation() {
 }
 
 #[test]
-#[cfg(any(feature = "std", feature = "alloc"))]
 fn event_modification() {
     let clock = ManualClock::new();
     let mut scheduler = make_scheduler!(&str, clock);
 
     let event_id = scheduler.schedule_in(VDuration::from_secs(10), "event_10s").unwrap();
-    scheduler.modify_event(event_id, Some(VInstant(VDuration::from_secs(5).0)), Some("event_5s"));
+    scheduler.modify_event(event_id, Some(VInstant(VDuration::from_secs(5).0)), Some("event_5s")).unwrap();
 
     let mut processed_events = Vec::new();
     let target_time = scheduler.now() + VDuration::from_secs(20);
@@ -147,3 +168,388 @@ fn event_modification() {
     assert_eq!(processed_events[0].1, "event_5s");
 }
 
+#[test]
+fn recurring_event_fires_every_period() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let _ = scheduler.schedule_every(VDuration::from_secs(2), "tick").unwrap();
+
+    let mut fired = Vec::new();
+    let target_time = scheduler.now() + VDuration::from_secs(7);
+    scheduler.run_until(target_time, |time, payload| {
+        fired.push((time.0 / 1_000_000_000, payload));
+    });
+
+    assert_eq!(fired, vec![(2, "tick"), (4, "tick"), (6, "tick")]);
+
+    // The series keeps going on the next run.
+    let target_time = scheduler.now() + VDuration::from_secs(2);
+    scheduler.run_until(target_time, |time, _| fired.push((time.0 / 1_000_000_000, "again")));
+    assert_eq!(fired.last(), Some(&(8, "again")));
+}
+
+#[test]
+fn recurring_event_respects_max_repetitions_and_end() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(u32, clock);
+
+    let limited = Recurrence::every(VDuration::from_secs(1)).with_max_repetitions(3);
+    let _ = scheduler.schedule_recurring(VDuration::from_secs(1), limited, 1).unwrap();
+
+    let bounded = Recurrence::every(VDuration::from_secs(1)).with_end(VInstant(VDuration::from_secs(2).0));
+    let _ = scheduler.schedule_recurring(VDuration::from_secs(1), bounded, 10).unwrap();
+
+    let mut sum = 0;
+    let mut count = 0;
+    let target_time = scheduler.now() + VDuration::from_secs(10);
+    scheduler.run_until(target_time, |_, payload| {
+        sum += payload;
+        count += 1;
+    });
+
+    assert_eq!(count, 5);
+    assert_eq!(sum, 3 + 20);
+}
+
+#[test]
+fn recurring_event_rejects_invalid_rules() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    assert_eq!(scheduler.schedule_every(VDuration::from_nanos(0), "zero"), Err(SchedulerError::InvalidRecurrence("zero")));
+
+    let no_reps = Recurrence::every(VDuration::from_secs(1)).with_max_repetitions(0);
+    assert_eq!(scheduler.schedule_recurring(VDuration::from_secs(1), no_reps, "none"), Err(SchedulerError::InvalidRecurrence("none")));
+
+    let ended = Recurrence::every(VDuration::from_secs(1)).with_end(VInstant(0));
+    assert_eq!(scheduler.schedule_recurring(VDuration::from_secs(1), ended, "late"), Err(SchedulerError::InvalidRecurrence("late")));
+}
+
+#[test]
+fn recurring_event_cancellation_stops_series() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let id = scheduler.schedule_every(VDuration::from_secs(1), "tick").unwrap();
+
+    let mut fired = 0;
+    let target_time = scheduler.now() + VDuration::from_secs(3);
+    scheduler.run_until(target_time, |_, _| fired += 1);
+    assert_eq!(fired, 3);
+
+    scheduler.cancel(id).unwrap();
+    let target_time = scheduler.now() + VDuration::from_secs(3);
+    scheduler.run_until(target_time, |_, _| fired += 1);
+    assert_eq!(fired, 3);
+}
+
+#[test]
+fn recurring_event_fixed_rate_vs_fixed_delay() {
+    fn run(mode: RepeatMode) -> Vec<u64> {
+        let clock = ManualClock::new();
+        let mut scheduler = make_scheduler!((), clock);
+        let rule = Recurrence::every(VDuration::from_secs(2)).with_mode(mode);
+        let _ = scheduler.schedule_recurring(VDuration::from_secs(2), rule, ()).unwrap();
+
+        // The first occurrence fires late: the clock is already past it.
+        scheduler.clock_mut().advance(VDuration::from_secs(3));
+
+        let mut fired = Vec::new();
+        scheduler.run_until(VInstant(VDuration::from_secs(10).0), |time, _| {
+            fired.push(time.0 / 1_000_000_000);
+        });
+        fired
+    }
+
+    assert_eq!(run(RepeatMode::FixedRate), vec![3, 4, 6, 8, 10]);
+    assert_eq!(run(RepeatMode::FixedDelay), vec![3, 5, 7, 9]);
+}
+
+#[test]
+fn schedule_at_absolute_time() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+    scheduler.clock_mut().advance(VDuration::from_secs(1));
+
+    let _ = scheduler.schedule_at(VInstant(VDuration::from_secs(4).0), "at_4s").unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_secs(2), "in_2s").unwrap();
+
+    let mut processed_events = Vec::new();
+    scheduler.run_until(VInstant(VDuration::from_secs(5).0), |time, payload| {
+        processed_events.push((time.0 / 1_000_000_000, payload));
+    });
+
+    assert_eq!(processed_events, vec![(3, "in_2s"), (4, "at_4s")]);
+}
+
+#[test]
+fn schedule_at_past_deadline_policies() {
+    let past = VInstant(VDuration::from_secs(1).0);
+
+    // Default: fires immediately, ahead of events due now, without moving the clock back.
+    let mut scheduler = make_scheduler!(&str, ManualClock::new());
+    scheduler.clock_mut().advance(VDuration::from_secs(2));
+    let _ = scheduler.schedule_in(VDuration::from_secs(0), "now").unwrap();
+    let _ = scheduler.schedule_at(past, "past").unwrap();
+    let mut processed_events = Vec::new();
+    let target_time = scheduler.now();
+    scheduler.run_until(target_time, |time, payload| processed_events.push((time, payload)));
+    assert_eq!(processed_events, vec![(target_time, "past"), (target_time, "now")]);
+    assert_eq!(scheduler.now(), target_time);
+
+    // Reject: the payload is handed back.
+    let mut scheduler = make_scheduler!(&str, ManualClock::new()).with_past_policy(PastDeadlinePolicy::Reject);
+    scheduler.clock_mut().advance(VDuration::from_secs(2));
+    assert_eq!(scheduler.schedule_at(past, "past"), Err(SchedulerError::DeadlineInPast("past")));
+
+    // Clamp: the deadline becomes `now`, queued behind events already due.
+    let mut scheduler = make_scheduler!(&str, ManualClock::new());
+    scheduler.set_past_policy(PastDeadlinePolicy::Clamp);
+    scheduler.clock_mut().advance(VDuration::from_secs(2));
+    let _ = scheduler.schedule_in(VDuration::from_secs(0), "now").unwrap();
+    let _ = scheduler.schedule_at(past, "past").unwrap();
+    let mut processed_events = Vec::new();
+    let target_time = scheduler.now();
+    scheduler.run_until(target_time, |_, payload| processed_events.push(payload));
+    assert_eq!(processed_events.len(), 2);
+    assert!(processed_events.contains(&"past"));
+}
+
+#[test]
+fn event_modification_rejected_in_past() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock).with_past_policy(PastDeadlinePolicy::Reject);
+    scheduler.clock_mut().advance(VDuration::from_secs(2));
+
+    let event_id = scheduler.schedule_in(VDuration::from_secs(10), "event").unwrap();
+    assert_eq!(
+        scheduler.modify_event(event_id, Some(VInstant(0)), Some("moved")),
+        Err(SchedulerError::DeadlineInPast(Some("moved")))
+    );
+    assert_eq!(scheduler.modify_event(event_id, Some(VInstant(VDuration::from_secs(3).0)), None), Ok(()));
+    assert_eq!(scheduler.modify_event(EventId(7), None, None), Err(SchedulerError::UnknownId(EventId(7))));
+}
+
+#[test]
+fn priorities_order_events_at_same_instant() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let at = VInstant(VDuration::from_secs(1).0);
+    let _ = scheduler.schedule_at_with_priority(at, Priority::LOW, "timer").unwrap();
+    let _ = scheduler.schedule_at(at, "default").unwrap();
+    let _ = scheduler.schedule_in_with_priority(VDuration::from_secs(1), Priority::HIGH, "network").unwrap();
+    let _ = scheduler.schedule_in_with_priority(VDuration::from_millis(500), Priority::LOWEST, "earlier").unwrap();
+    let _ = scheduler.schedule_in_with_priority(VDuration::from_secs(1), Priority::HIGH, "network_2").unwrap();
+
+    let mut processed_events = Vec::new();
+    let target_time = scheduler.now() + VDuration::from_secs(2);
+    scheduler.run_until(target_time, |_, payload| processed_events.push(payload));
+
+    assert_eq!(processed_events, vec!["earlier", "network", "network_2", "default", "timer"]);
+}
+
+#[test]
+fn recurring_event_keeps_priority() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let rule = Recurrence::every(VDuration::from_secs(1)).with_max_repetitions(2);
+    let _ = scheduler.schedule_recurring_with_priority(VDuration::from_secs(1), rule, Priority::HIGHEST, "tick").unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_secs(2), "other").unwrap();
+
+    let mut processed_events = Vec::new();
+    let target_time = scheduler.now() + VDuration::from_secs(2);
+    scheduler.run_until(target_time, |_, payload| processed_events.push(payload));
+
+    assert_eq!(processed_events, vec!["tick", "tick", "other"]);
+}
+
+#[test]
+fn priority_can_be_changed() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let _ = scheduler.schedule_in(VDuration::from_secs(1), "first").unwrap();
+    let second = scheduler.schedule_in(VDuration::from_secs(1), "second").unwrap();
+    assert_eq!(scheduler.set_priority(second, Priority::HIGH), Ok(()));
+    assert_eq!(scheduler.set_priority(EventId(99), Priority::HIGH), Err(SchedulerError::UnknownId(EventId(99))));
+
+    let mut processed_events = Vec::new();
+    let target_time = scheduler.now() + VDuration::from_secs(1);
+    scheduler.run_until(target_time, |_, payload| processed_events.push(payload));
+
+    assert_eq!(processed_events, vec!["second", "first"]);
+}
+
+#[test]
+fn step_fires_one_event_at_a_time() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    assert_eq!(scheduler.peek_next(), None);
+    assert!(scheduler.step().is_none());
+
+    let second = scheduler.schedule_in(VDuration::from_secs(2), "second").unwrap();
+    let first = scheduler.schedule_in(VDuration::from_secs(1), "first").unwrap();
+    assert_eq!(scheduler.peek_next(), Some(VInstant(VDuration::from_secs(1).0)));
+
+    let ev = scheduler.step().unwrap();
+    assert_eq!((ev.id, ev.payload), (first, "first"));
+    assert_eq!(scheduler.now(), ev.at);
+    assert_eq!(scheduler.peek_next(), Some(VInstant(VDuration::from_secs(2).0)));
+
+    let ev = scheduler.step().unwrap();
+    assert_eq!((ev.id, ev.payload), (second, "second"));
+    assert_eq!(scheduler.now().0 / 1_000_000_000, 2);
+    assert!(scheduler.step().is_none());
+}
+
+#[test]
+fn peek_next_skips_cancelled_events() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let first = scheduler.schedule_in(VDuration::from_secs(1), "first").unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_secs(3), "third").unwrap();
+    scheduler.cancel(first).unwrap();
+
+    assert_eq!(scheduler.peek_next(), Some(VInstant(VDuration::from_secs(3).0)));
+    assert_eq!(scheduler.step().map(|ev| ev.payload), Some("third"));
+}
+
+#[test]
+fn run_for_advances_relative_to_now() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+    scheduler.clock_mut().advance(VDuration::from_secs(10));
+
+    let _ = scheduler.schedule_in(VDuration::from_secs(1), "inside").unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_secs(5), "outside").unwrap();
+
+    let mut processed_events = Vec::new();
+    scheduler.run_for(VDuration::from_secs(2), |_, payload| processed_events.push(payload));
+
+    assert_eq!(processed_events, vec!["inside"]);
+    assert_eq!(scheduler.now().0 / 1_000_000_000, 12);
+}
+
+#[test]
+fn run_until_idle_drains_and_caps() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let _ = scheduler.schedule_in(VDuration::from_secs(1), "a").unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_secs(4), "b").unwrap();
+    let mut processed_events = Vec::new();
+    let fired = scheduler.run_until_idle(100, |_, payload| processed_events.push(payload));
+    assert_eq!(fired, 2);
+    assert_eq!(processed_events, vec!["a", "b"]);
+    assert_eq!(scheduler.now().0 / 1_000_000_000, 4);
+    assert_eq!(scheduler.peek_next(), None);
+
+    // A never-ending series stops at the cap.
+    let _ = scheduler.schedule_every(VDuration::from_secs(1), "tick").unwrap();
+    let fired = scheduler.run_until_idle(10, |_, _| {});
+    assert_eq!(fired, 10);
+    assert_eq!(scheduler.now().0 / 1_000_000_000, 14);
+    assert!(scheduler.peek_next().is_some());
+}
+
+#[test]
+fn handlers_can_cascade_within_run() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(u32, clock);
+
+    let _ = scheduler.schedule_in(VDuration::from_secs(1), 3).unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_millis(2500), 100).unwrap();
+
+    let mut processed_events = Vec::new();
+    let target_time = scheduler.now() + VDuration::from_secs(10);
+    scheduler.run_until_with(target_time, |ctx, hops| {
+        processed_events.push((ctx.now().0 / 1_000_000, hops));
+        if hops > 0 && hops < 100 {
+            // Follow-ups due before the target fire within this run, in time order.
+            let _ = ctx.schedule_in(VDuration::from_secs(1), hops - 1).unwrap();
+        }
+    });
+
+    assert_eq!(processed_events, vec![(1000, 3), (2000, 2), (2500, 100), (3000, 1), (4000, 0)]);
+    assert_eq!(scheduler.now(), target_time);
+}
+
+#[test]
+fn handlers_can_cancel_and_schedule_at() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let victim = scheduler.schedule_in(VDuration::from_secs(3), "victim").unwrap();
+    let _ = scheduler.schedule_every(VDuration::from_secs(1), "tick").unwrap();
+
+    let mut processed_events = Vec::new();
+    let target_time = scheduler.now() + VDuration::from_secs(5);
+    scheduler.run_until_with(target_time, |ctx, payload| {
+        processed_events.push(payload);
+        if payload == "tick" && ctx.now().0 == VDuration::from_secs(2).0 {
+            ctx.cancel(victim).unwrap();
+            // Stop the recurring series from inside its own handler.
+            let current = ctx.event_id();
+            ctx.cancel(current).unwrap();
+            let at = ctx.now();
+            let _ = ctx.schedule_at_with_priority(at, Priority::HIGH, "urgent").unwrap();
+        }
+    });
+
+    assert_eq!(processed_events, vec!["tick", "tick", "urgent"]);
+}
+
+#[test]
+fn len_is_empty_and_clear() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+    assert!(scheduler.is_empty());
+
+    let a = scheduler.schedule_in(VDuration::from_secs(1), "a").unwrap();
+    let _ = scheduler.schedule_every(VDuration::from_secs(1), "tick").unwrap();
+    assert_eq!(scheduler.len(), 2);
+
+    scheduler.cancel(a).unwrap();
+    assert_eq!(scheduler.len(), 1);
+
+    scheduler.clear();
+    assert!(scheduler.is_empty());
+    assert!(scheduler.step().is_none());
+}
+
+#[test]
+fn cancel_reports_whether_pending() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+
+    let a = scheduler.schedule_in(VDuration::from_secs(1), "a").unwrap();
+    let b = scheduler.schedule_in(VDuration::from_secs(2), "b").unwrap();
+    let tick = scheduler.schedule_every(VDuration::from_secs(1), "tick").unwrap();
+
+    let target_time = scheduler.now() + VDuration::from_secs(1);
+    scheduler.run_until(target_time, |_, _| {});
+
+    assert_eq!(scheduler.cancel(a), Err(SchedulerError::NotPending(a)));
+    assert_eq!(scheduler.cancel(b), Ok("b"));
+    assert_eq!(scheduler.cancel(b), Err(SchedulerError::NotPending(b)));
+    assert_eq!(scheduler.cancel(tick), Ok("tick"));
+    assert_eq!(scheduler.cancel(EventId(42)), Err(SchedulerError::UnknownId(EventId(42))));
+    assert!(scheduler.is_empty());
+}
+
+#[test]
+fn scheduling_past_the_end_of_time_overflows() {
+    let clock = ManualClock::new();
+    let mut scheduler = make_scheduler!(&str, clock);
+    scheduler.clock_mut().advance(VDuration::from_secs(1));
+
+    let err = scheduler.schedule_in(VDuration(u64::MAX), "late").unwrap_err();
+    assert_eq!(err, SchedulerError::ClockOverflow("late"));
+    assert_eq!(err.into_payload(), Some("late"));
+    assert!(scheduler.is_empty());
+}
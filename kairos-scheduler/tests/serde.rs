@@ -0,0 +1,23 @@
+#![cfg(feature = "serde")]
+
+use kairos_core::{ManualClock, VDuration, VInstant};
+use kairos_scheduler::{EventId, Scheduler};
+
+#[test]
+fn event_ids_serialize_as_numbers() {
+    let mut sched = Scheduler::new(ManualClock::new());
+    let ids: Vec<EventId> = (0..3).map(|i| sched.schedule_in(VDuration::from_secs(i), i).unwrap()).collect();
+
+    let json = serde_json::to_string(&ids).unwrap();
+    assert_eq!(json, "[0,1,2]");
+    let back: Vec<EventId> = serde_json::from_str(&json).unwrap();
+    sched.cancel(back[1]).unwrap();
+
+    // Ids and instants together, e.g. in a log of fired events.
+    let mut log = Vec::new();
+    sched.run_until(VInstant(VDuration::from_secs(2).0), |at, p| log.push((at, p)));
+    assert_eq!(
+        serde_json::to_string(&log).unwrap(),
+        r#"[["1970-01-01T00:00:00Z",0],["1970-01-01T00:00:02Z",2]]"#
+    );
+}
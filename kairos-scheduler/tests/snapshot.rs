@@ -0,0 +1,233 @@
+use kairos_core::{ManualClock, VDuration, VInstant};
+use kairos_scheduler::storage::HeaplessStorage;
+use kairos_scheduler::{
+    ByteSink, DecodeError, EncodeError, PayloadCodec, Priority, Reader, Recurrence, RepeatMode, RestoreError, Scheduler,
+    SnapshotStorage,
+};
+
+type Small<T> = Scheduler<T, ManualClock, HeaplessStorage<T, 16>>;
+type Buf = heapless::Vec<u8, 512>;
+
+fn small<T>() -> Small<T> {
+    Scheduler::with_storage(ManualClock::new(), HeaplessStorage::new())
+}
+
+fn ms(n: u64) -> VInstant {
+    VInstant(VDuration::from_millis(n).0)
+}
+
+/// User payload with a hand-written codec.
+#[derive(Debug, Clone, PartialEq)]
+struct Job {
+    name: u8,
+    weight: i32,
+}
+
+impl PayloadCodec for Job {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) -> Result<(), EncodeError> {
+        self.name.encode(out)?;
+        self.weight.encode(out)
+    }
+
+    fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError> {
+        Ok(Job { name: u8::decode(r)?, weight: i32::decode(r)? })
+    }
+}
+
+/// One-shots with ties and priorities, a moved and a cancelled event, and two
+/// recurring series in different modes.
+fn setup(sched: &mut Small<Job>) {
+    let job = |name, weight| Job { name, weight };
+    sched.schedule_in(VDuration::from_millis(300), job(1, -5)).unwrap();
+    sched.schedule_in(VDuration::from_millis(300), job(2, 7)).unwrap();
+    sched.schedule_in_with_priority(VDuration::from_millis(300), Priority::HIGH, job(3, 0)).unwrap();
+    let moved = sched.schedule_in(VDuration::from_millis(100), job(4, 1)).unwrap();
+    sched.modify_event(moved, Some(ms(900)), None).unwrap();
+    let gone = sched.schedule_in(VDuration::from_millis(500), job(5, 1)).unwrap();
+    sched.cancel(gone).unwrap();
+    let rate = Recurrence::every(VDuration::from_millis(250)).with_max_repetitions(6);
+    sched.schedule_recurring(VDuration::from_millis(250), rate, job(6, 100)).unwrap();
+    let delay = Recurrence::every(VDuration::from_millis(400)).with_mode(RepeatMode::FixedDelay).with_end(ms(1800));
+    sched.schedule_recurring(VDuration::from_millis(200), delay, job(7, -100)).unwrap();
+}
+
+/// Fire everything up to `end`, scheduling a follow-up after each firing so ids and
+/// sequence numbers keep being handed out.
+fn run(sched: &mut Small<Job>, end: VInstant, fired: &mut Vec<(u64, u8, u64)>) {
+    sched.run_until_with(end, |ctx, job| {
+        fired.push((ctx.now().0 / 1_000_000, job.name, ctx.event_id().0));
+        if job.name < 10 {
+            let _ = ctx.schedule_in(VDuration::from_millis(350), Job { name: job.name + 10, weight: job.weight });
+        }
+    });
+}
+
+#[test]
+fn restored_run_continues_identically() {
+    let mut whole = small();
+    setup(&mut whole);
+    let mut expected = Vec::new();
+    run(&mut whole, ms(3000), &mut expected);
+
+    let mut first = small();
+    setup(&mut first);
+    let mut fired = Vec::new();
+    run(&mut first, ms(1100), &mut fired);
+    let mut bytes = Buf::new();
+    first.snapshot(&mut bytes).unwrap();
+    drop(first);
+
+    let mut second = small();
+    second.restore(&bytes).unwrap();
+    assert_eq!(second.now(), ms(1100));
+    run(&mut second, ms(3000), &mut fired);
+
+    assert_eq!(fired, expected);
+    assert_eq!(second.now(), whole.now());
+    // A fresh event gets the same id either way.
+    let a = whole.schedule_in(VDuration(0), Job { name: 0, weight: 0 });
+    let b = second.schedule_in(VDuration(0), Job { name: 0, weight: 0 });
+    assert_eq!(a.unwrap(), b.unwrap());
+}
+
+/// Restore `bytes`, expect `err`, and check nothing changed.
+fn rejects<S: SnapshotStorage<u8>>(mut sched: Scheduler<u8, ManualClock, S>, bytes: &[u8], err: RestoreError) {
+    let kept = sched.schedule_in(VDuration::from_secs(1), 99).unwrap();
+    assert_eq!(sched.restore(bytes), Err(err));
+    assert_eq!(sched.now(), VInstant(0));
+    assert_eq!(sched.len(), 1);
+    assert_eq!(sched.cancel(kept), Ok(99));
+    // Counters are untouched too: the next id follows `kept`.
+    assert_eq!(sched.schedule_in(VDuration(0), 0).unwrap().0, kept.0 + 1);
+}
+
+#[test]
+fn restore_rejects_bad_input() {
+    let mut sched = small();
+    setup(&mut sched);
+    sched.run_until(ms(500), |_, _| {});
+    let mut bytes = Buf::new();
+    sched.snapshot(&mut bytes).unwrap();
+    let mut one_shot = small::<u8>();
+    one_shot.run_until(ms(5), |_, _| {});
+    one_shot.schedule_in(VDuration::from_secs(1), 7).unwrap();
+    let mut valid = Buf::new();
+    one_shot.snapshot(&mut valid).unwrap();
+    let malformed = |offset| RestoreError::Malformed(DecodeError { offset });
+
+    // Not a snapshot, a truncated header, and a truncated event.
+    rejects(small(), b"KTR1", malformed(0));
+    rejects(small(), &valid[..6], malformed(6));
+    rejects(small(), &valid[..valid.len() - 1], malformed(valid.len() - 1));
+    // Trailing bytes after the last event.
+    let mut long = valid.clone();
+    long.push(0).unwrap();
+    rejects(small(), &long, malformed(valid.len()));
+
+    // The clock cannot be rewound to the snapshot.
+    let mut late = small::<Job>();
+    late.run_until(ms(600), |_, _| {});
+    assert_eq!(late.restore(&bytes), Err(RestoreError::ClockAhead { snapshot: ms(500), clock: ms(600) }));
+    let mut later = small::<u8>();
+    later.run_until(ms(6), |_, _| {});
+    let kept = later.schedule_in(VDuration::from_secs(1), 99).unwrap();
+    assert_eq!(later.restore(&valid), Err(RestoreError::ClockAhead { snapshot: ms(5), clock: ms(6) }));
+    assert_eq!(later.cancel(kept), Ok(99));
+
+    // Too many events for the backend: `rejects` schedules one more than the snapshot holds.
+    let tiny = || Scheduler::<u8, _, _>::with_storage(ManualClock::new(), HeaplessStorage::<u8, 1>::new());
+    let mut two = small::<u8>();
+    two.schedule_in(VDuration::from_secs(1), 1).unwrap();
+    two.schedule_in(VDuration::from_secs(2), 2).unwrap();
+    let mut full = Buf::new();
+    two.snapshot(&mut full).unwrap();
+    rejects(tiny(), &full, RestoreError::CapacityFull);
+}
+
+/// Two events in one snapshot: magic, now, next id, next seq, count, then per event
+/// id, deadline, priority, seq, flags and a `u8` payload.
+const TWO_EVENTS: [u8; 20] = [b'K', b'S', b'N', b'1', 0, 5, 5, 2, 1, 10, 0, 1, 0, 7, 2, 20, 0, 2, 0, 8];
+
+/// Repeated ids or sequence numbers are rejected by every backend.
+fn rejects_repeats<S: SnapshotStorage<u8>>(new: impl Fn() -> S) {
+    let sched = || Scheduler::with_storage(ManualClock::new(), new());
+    let malformed = RestoreError::Malformed(DecodeError { offset: 14 });
+
+    let mut same_id = TWO_EVENTS;
+    same_id[14] = 1;
+    rejects(sched(), &same_id, malformed);
+    // Same seq, and with it the same (deadline, priority, seq) key.
+    let mut same_key = TWO_EVENTS;
+    same_key[15] = 10;
+    same_key[17] = 1;
+    rejects(sched(), &same_key, malformed);
+    // Same seq at another deadline.
+    let mut same_seq = TWO_EVENTS;
+    same_seq[17] = 1;
+    rejects(sched(), &same_seq, malformed);
+
+    let mut ok = sched();
+    ok.restore(&TWO_EVENTS).unwrap();
+    let mut fired = Vec::new();
+    ok.run_until_idle(10, |_, payload| fired.push(payload));
+    assert_eq!(fired, [7, 8]);
+    assert!(ok.is_empty());
+}
+
+#[test]
+fn restore_rejects_repeated_ids_and_seqs() {
+    rejects_repeats(HeaplessStorage::<u8, 4>::new);
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    rejects_repeats(kairos_scheduler::storage::PqStorage::new);
+    #[cfg(feature = "timer-wheel")]
+    rejects_repeats(kairos_scheduler::storage::WheelStorage::new);
+    #[cfg(feature = "calendar-queue")]
+    rejects_repeats(kairos_scheduler::storage::CalendarQueueStorage::new);
+}
+
+#[test]
+fn snapshot_reports_full_buffer() {
+    let mut sched = small();
+    setup(&mut sched);
+    let mut bytes = heapless::Vec::<u8, 16>::new();
+    assert_eq!(sched.snapshot(&mut bytes), Err(EncodeError));
+}
+
+#[test]
+fn integer_codecs_round_trip() {
+    let mut bytes = Buf::new();
+    (-1i64).encode(&mut bytes).unwrap();
+    u64::MAX.encode(&mut bytes).unwrap();
+    i32::MIN.encode(&mut bytes).unwrap();
+    300u16.encode(&mut bytes).unwrap();
+    true.encode(&mut bytes).unwrap();
+    // Zigzag keeps small negative numbers to one byte; u64::MAX takes ten.
+    assert_eq!(bytes.len(), 1 + 10 + 5 + 2 + 1);
+
+    let mut r = Reader::new(&bytes);
+    assert_eq!(i64::decode(&mut r), Ok(-1));
+    assert_eq!(u64::decode(&mut r), Ok(u64::MAX));
+    assert_eq!(i32::decode(&mut r), Ok(i32::MIN));
+    assert_eq!(u8::decode(&mut r), Err(DecodeError { offset: 16 }));
+    assert!(r.finish().is_err());
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[test]
+fn snapshots_move_between_backends() {
+    let mut small = small();
+    setup(&mut small);
+    small.run_until(ms(700), |_, _| {});
+    let mut bytes = Vec::new();
+    small.snapshot(&mut bytes).unwrap();
+
+    let mut big = Scheduler::<Job, _>::new(ManualClock::new());
+    big.restore(&bytes).unwrap();
+    assert_eq!(big.len(), small.len());
+
+    let mut from_small = Vec::new();
+    small.run_until_idle(100, |at, job| from_small.push((at, job)));
+    let mut from_big = Vec::new();
+    big.run_until_idle(100, |at, job| from_big.push((at, job)));
+    assert_eq!(from_big, from_small);
+}
@@ -0,0 +1,198 @@
+use kairos_core::{ManualClock, VDuration, VInstant};
+use kairos_scheduler::storage::HeaplessStorage;
+#[cfg(feature = "timer-wheel")]
+use kairos_scheduler::storage::WheelStorage;
+#[cfg(feature = "calendar-queue")]
+use kairos_scheduler::storage::CalendarQueueStorage;
+use kairos_scheduler::{Event, EventId, Priority, Recurrence, Scheduler, SchedulerError, Slot, Storage};
+
+/// Minimal user-provided backend: an unsorted list scanned on every pop.
+struct ListStorage<T> {
+    events: Vec<Event<Slot<T>>>,
+}
+
+impl<T> Storage<T> for ListStorage<T> {
+    fn push(&mut self, event: Event<Slot<T>>) -> Result<(), Event<Slot<T>>> {
+        self.events.push(event);
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<VInstant> {
+        self.events.iter().max().map(|ev| ev.at)
+    }
+
+    fn pop(&mut self) -> Option<Event<Slot<T>>> {
+        let (i, _) = self.events.iter().enumerate().max_by(|a, b| a.1.cmp(b.1))?;
+        Some(self.events.swap_remove(i))
+    }
+
+    fn remove(&mut self, id: EventId) -> Option<Event<Slot<T>>> {
+        let i = self.events.iter().position(|ev| ev.id == id)?;
+        Some(self.events.swap_remove(i))
+    }
+
+    fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+/// Same workload on any backend; returns (time, payload) of every firing.
+fn workload<S: Storage<u32>>(mut scheduler: Scheduler<u32, ManualClock, S>) -> Vec<(u64, u32)> {
+    for i in 0..8u32 {
+        let _ = scheduler.schedule_in(VDuration::from_millis(u64::from(i % 3) * 500), i).unwrap();
+    }
+    let high = scheduler.schedule_in(VDuration::from_millis(500), 100).unwrap();
+    scheduler.set_priority(high, Priority::HIGHEST).unwrap();
+    let moved = scheduler.schedule_in(VDuration::from_secs(9), 200).unwrap();
+    scheduler.modify_event(moved, Some(VInstant(VDuration::from_millis(500).0)), Some(201)).unwrap();
+    let cancelled = scheduler.schedule_in(VDuration::from_secs(1), 300).unwrap();
+    scheduler.cancel(cancelled).unwrap();
+    let rule = Recurrence::every(VDuration::from_millis(700)).with_max_repetitions(3);
+    let _ = scheduler.schedule_recurring(VDuration::from_millis(500), rule, 400).unwrap();
+
+    let mut fired = Vec::new();
+    scheduler.run_until(VInstant(VDuration::from_secs(5).0), |time, payload| {
+        fired.push((time.0 / 1_000_000, payload));
+    });
+    fired
+}
+
+#[test]
+fn backends_fire_identically() {
+    let expected = workload(Scheduler::with_storage(ManualClock::new(), HeaplessStorage::<u32, 32>::new()));
+    assert_eq!(
+        expected,
+        vec![
+            (0, 0), (0, 3), (0, 6),
+            (500, 100), (500, 1), (500, 4), (500, 7), (500, 201), (500, 400),
+            (1000, 2), (1000, 5),
+            (1200, 400), (1900, 400),
+        ]
+    );
+
+    let custom = workload(Scheduler::with_storage(ManualClock::new(), ListStorage { events: Vec::new() }));
+    assert_eq!(custom, expected);
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    assert_eq!(workload(Scheduler::new(ManualClock::new())), expected);
+
+    #[cfg(feature = "timer-wheel")]
+    assert_eq!(workload(Scheduler::with_storage(ManualClock::new(), WheelStorage::new())), expected);
+    // Ticks coarser than the gaps between events must not change the order.
+    #[cfg(feature = "timer-wheel")]
+    assert_eq!(
+        workload(Scheduler::with_storage(ManualClock::new(), WheelStorage::with_resolution(VDuration::from_secs(1), 2))),
+        expected
+    );
+
+    #[cfg(feature = "calendar-queue")]
+    assert_eq!(workload(Scheduler::with_storage(ManualClock::new(), CalendarQueueStorage::new())), expected);
+}
+
+/// Pseudo-random schedule/cancel/run mix with a fixed seed; returns every firing.
+#[cfg(any(feature = "timer-wheel", feature = "calendar-queue"))]
+fn churn<S: Storage<u32>>(mut scheduler: Scheduler<u32, ManualClock, S>) -> Vec<(u64, u32)> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut ids = Vec::new();
+    let mut fired = Vec::new();
+    for i in 0..2_000u32 {
+        let r = next();
+        match r % 4 {
+            0 | 1 => {
+                // Mostly short deadlines, a few far beyond the wheel span.
+                let dur = if r % 97 == 0 { (r >> 8) % (1 << 40) } else { (r >> 8) % 100_000 };
+                let priority = Priority((r >> 32) as u8 % 3 * 64);
+                ids.push(scheduler.schedule_in_with_priority(VDuration::from_nanos(dur), priority, i).unwrap());
+            }
+            2 if !ids.is_empty() => {
+                let id = ids.swap_remove((r >> 16) as usize % ids.len());
+                let _ = scheduler.cancel(id);
+            }
+            _ => {
+                let target = scheduler.now() + VDuration::from_nanos((r >> 8) % 50_000);
+                scheduler.run_until(target, |time, payload| fired.push((time.0, payload)));
+            }
+        }
+    }
+    let _ = scheduler.run_until_idle(usize::MAX, |time, payload| fired.push((time.0, payload)));
+    fired
+}
+
+#[cfg(feature = "timer-wheel")]
+#[test]
+fn wheel_matches_heap_under_churn() {
+    let expected = churn(Scheduler::new(ManualClock::new()));
+    assert!(expected.len() > 500);
+    for (tick, levels) in [(1, 6), (1_000, 2), (7, 1), (1 << 20, 11)] {
+        let wheel = WheelStorage::with_resolution(VDuration::from_nanos(tick), levels);
+        assert_eq!(churn(Scheduler::with_storage(ManualClock::new(), wheel)), expected, "tick {tick}, levels {levels}");
+    }
+}
+
+#[test]
+fn heapless_storage_reports_full() {
+    let mut scheduler = Scheduler::with_storage(ManualClock::new(), HeaplessStorage::<&str, 2>::new());
+    let _ = scheduler.schedule_in(VDuration::from_secs(1), "a").unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_secs(2), "b").unwrap();
+    assert_eq!(scheduler.schedule_in(VDuration::from_secs(3), "c"), Err(SchedulerError::CapacityFull("c")));
+}
+
+#[test]
+fn heapless_cancel_frees_capacity_immediately() {
+    let mut scheduler = Scheduler::with_storage(ManualClock::new(), HeaplessStorage::<&str, 2>::new());
+    let a = scheduler.schedule_in(VDuration::from_secs(1), "a").unwrap();
+    let _ = scheduler.schedule_in(VDuration::from_secs(2), "b").unwrap();
+
+    assert_eq!(scheduler.cancel(a), Ok("a"));
+    let c = scheduler.schedule_in(VDuration::from_secs(3), "c").unwrap();
+    assert_eq!(scheduler.len(), 2);
+
+    // Unknown, already-cancelled and already-fired ids are reported, never panic.
+    assert_eq!(scheduler.cancel(EventId(1_000)), Err(SchedulerError::UnknownId(EventId(1_000))));
+    assert_eq!(scheduler.cancel(a), Err(SchedulerError::NotPending(a)));
+    for i in 0..64 {
+        assert!(scheduler.cancel(EventId(10_000 + i)).is_err());
+    }
+    let _ = scheduler.step();
+    assert_eq!(scheduler.cancel(EventId(1)), Err(SchedulerError::NotPending(EventId(1))));
+    assert_eq!(scheduler.cancel(c), Ok("c"));
+    assert!(scheduler.is_empty());
+}
+
+#[cfg(feature = "calendar-queue")]
+#[test]
+fn calendar_queue_matches_heap_under_churn() {
+    let expected = churn(Scheduler::new(ManualClock::new()));
+    assert_eq!(churn(Scheduler::with_storage(ManualClock::new(), CalendarQueueStorage::new())), expected);
+}
+
+#[cfg(feature = "calendar-queue")]
+#[test]
+fn calendar_queue_resizes_with_population() {
+    let mut scheduler = Scheduler::with_storage(ManualClock::new(), CalendarQueueStorage::new());
+    for i in 0..10_000u32 {
+        let _ = scheduler.schedule_in(VDuration::from_micros(u64::from(i) * 10), i).unwrap();
+    }
+    let mut fired = 0u32;
+    let _ = scheduler.run_until_idle(5_000, |_, payload| {
+        assert_eq!(payload, fired);
+        fired += 1;
+    });
+    assert_eq!(scheduler.len(), 5_000);
+    let _ = scheduler.run_until_idle(usize::MAX, |_, payload| {
+        assert_eq!(payload, fired);
+        fired += 1;
+    });
+    assert_eq!(fired, 10_000);
+    assert!(scheduler.is_empty());
+}
@@ -0,0 +1,103 @@
+#![cfg(any(feature = "std", feature = "alloc"))]
+
+use kairos_core::{ManualClock, VDuration, VInstant};
+use kairos_scheduler::{DecodeError, EventId, Scheduler, Trace};
+
+fn secs(s: u64) -> VInstant {
+    VInstant(VDuration::from_secs(s).0)
+}
+
+/// Three one-shots plus a recurring tick; `twist` changes one payload.
+fn build(twist: bool) -> Scheduler<u32, ManualClock> {
+    let mut sched = Scheduler::new(ManualClock::new());
+    sched.schedule_in(VDuration::from_secs(1), 10).unwrap();
+    sched.schedule_in(VDuration::from_secs(2), if twist { 21 } else { 20 }).unwrap();
+    sched.schedule_in(VDuration::from_secs(3), 30).unwrap();
+    sched.schedule_every(VDuration::from_millis(1500), 99).unwrap();
+    sched
+}
+
+fn record() -> Trace {
+    let mut trace = Trace::new();
+    build(false).run_until_recorded(secs(5), &mut trace, |_, _| {});
+    trace
+}
+
+#[test]
+fn identical_runs_verify() {
+    let trace = record();
+    assert_eq!(trace.len(), 6);
+    assert_eq!(trace.entries()[0].id, EventId(0));
+    assert_eq!(trace.entries()[1].at, VInstant(1_500_000_000));
+
+    for _ in 0..100 {
+        let mut sched = build(false);
+        let mut verifier = trace.verifier();
+        let mut fired = 0;
+        sched.run_until_verified(secs(5), &mut verifier, |_, _| fired += 1).unwrap();
+        verifier.finish().unwrap();
+        assert_eq!(fired, 6);
+        assert_eq!(sched.now(), secs(5));
+    }
+    assert_eq!(record().digest(), trace.digest());
+}
+
+#[test]
+fn verification_stops_at_first_divergence() {
+    let trace = record();
+    let mut sched = build(true);
+    let mut verifier = trace.verifier();
+    let mut handled = Vec::new();
+
+    let div = sched.run_until_verified(secs(5), &mut verifier, |_, p| handled.push(p)).unwrap_err();
+
+    // 10 @1s and 99 @1.5s match; 21 @2s is caught before reaching the handler.
+    assert_eq!(handled, vec![10, 99]);
+    assert_eq!(div.index, 2);
+    let (expected, actual) = (div.expected.unwrap(), div.actual.unwrap());
+    assert_eq!((expected.id, expected.at), (actual.id, actual.at));
+    assert_ne!(expected.digest, actual.digest);
+    assert_eq!(sched.now(), secs(2));
+
+    let report = div.to_string();
+    assert!(report.starts_with("run diverged from trace at event #2\n"));
+    assert!(report.contains("  - expected: id 1 at 2000000000ns digest 0x"));
+    assert!(report.contains("  + actual:   id 1 at 2000000000ns digest 0x"));
+}
+
+#[test]
+fn short_and_long_runs_diverge() {
+    let trace = record();
+
+    // Stopping early leaves recorded events unmatched.
+    let mut verifier = trace.verifier();
+    build(false).run_until_verified(secs(2), &mut verifier, |_, _| {}).unwrap();
+    assert_eq!(verifier.matched(), 3);
+    let div = verifier.finish().unwrap_err();
+    assert_eq!((div.index, div.actual), (3, None));
+    assert_eq!(div.expected.unwrap().at, VInstant(3_000_000_000));
+
+    // Running past the recording fires events the trace never saw.
+    let mut longer = Trace::new();
+    build(false).run_until_recorded(secs(6), &mut longer, |_, _| {});
+    let div = trace.diff(&longer).unwrap();
+    assert_eq!((div.index, div.expected), (6, None));
+    assert!(div.to_string().contains("expected: end of trace"));
+    assert_eq!(trace.diff(&record()), None);
+}
+
+#[test]
+fn bytes_round_trip() {
+    let trace = record();
+    let bytes = trace.to_bytes();
+    // Magic, count, then 1 id byte + at most 5 delta bytes + 8 digest bytes per event.
+    assert!(bytes.len() <= 4 + 1 + 6 * 14);
+    assert_eq!(Trace::from_bytes(&bytes).unwrap(), trace);
+
+    assert_eq!(Trace::from_bytes(&bytes[..bytes.len() - 1]), Err(DecodeError { offset: bytes.len() - 8 }));
+    assert_eq!(Trace::from_bytes(b"nope"), Err(DecodeError { offset: 0 }));
+    let mut extra = bytes.clone();
+    extra.push(0);
+    assert_eq!(Trace::from_bytes(&extra), Err(DecodeError { offset: bytes.len() }));
+    assert_eq!(Trace::from_bytes(&Trace::new().to_bytes()).unwrap(), Trace::new());
+}